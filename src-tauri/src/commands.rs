@@ -4,6 +4,10 @@ use rusqlite::Connection;
 use tauri::State;
 
 use crate::engine::achievements;
+use crate::engine::backgrounds::{self, CharacterOptions, CreationOptions, NewCharacter};
+use crate::engine::classes;
+use crate::engine::meta;
+use crate::engine::recipes;
 use crate::engine::entity::*;
 use crate::engine::state::World;
 use crate::persistence::{config, database, save};
@@ -14,7 +18,19 @@ pub struct AppState {
 }
 
 #[tauri::command]
-pub fn new_game(seed: Option<String>, class: Option<String>, modifiers: Option<Vec<String>>, state: State<'_, AppState>) -> Result<TurnResult, String> {
+pub fn new_game(
+    seed: Option<String>,
+    class: Option<String>,
+    modifiers: Option<Vec<String>>,
+    pure: Option<bool>,
+    character: Option<NewCharacter>,
+    state: State<'_, AppState>,
+) -> Result<TurnResult, String> {
+    let character = match character {
+        Some(c) => CharacterOptions::parse(c.name.as_deref(), c.background.as_deref(), Some(c.allocation))?,
+        None => CharacterOptions::default(),
+    };
+
     let seed_val: u64 = match seed {
         Some(s) if !s.is_empty() => s.parse().unwrap_or_else(|_| {
             // Hash the string to get a seed
@@ -33,11 +49,7 @@ pub fn new_game(seed: Option<String>, class: Option<String>, modifiers: Option<V
         }
     };
 
-    let player_class = match class.as_deref() {
-        Some("Rogue") => PlayerClass::Rogue,
-        Some("Mage") => PlayerClass::Mage,
-        _ => PlayerClass::Warrior,
-    };
+    let player_class = classes::parse_class(class.as_deref().unwrap_or_default());
 
     let run_modifiers: Vec<RunModifier> = modifiers.unwrap_or_default().iter().filter_map(|m| {
         match m.as_str() {
@@ -45,20 +57,33 @@ pub fn new_game(seed: Option<String>, class: Option<String>, modifiers: Option<V
             "Marathon" => Some(RunModifier::Marathon),
             "Pacifist" => Some(RunModifier::Pacifist),
             "Cursed" => Some(RunModifier::Cursed),
+            "Famine" => Some(RunModifier::Famine),
             _ => None,
         }
     }).collect();
 
+    let pure = pure.unwrap_or(false);
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    if !meta::is_class_unlocked(&db, player_class) {
+        return Err(format!("The {:?} class is locked.", player_class));
+    }
+    if let Some(m) = run_modifiers.iter().find(|m| !meta::is_modifier_unlocked(&db, **m)) {
+        return Err(format!("The {:?} modifier is locked.", m));
+    }
+
     let mut world = World::new_with_class(seed_val, player_class, run_modifiers);
+    world.apply_character(&character);
+    world.pure_run = pure;
 
-    // Add unlocked achievement rewards to starting inventory
-    {
-        let db = state.db.lock().map_err(|e| e.to_string())?;
+    // Pure runs skip achievement rewards and meta bonuses
+    if !pure {
         let rewards = achievements::get_unlocked_rewards(&db);
         if !rewards.is_empty() {
             world.add_unlocked_rewards(rewards);
         }
+        world.apply_meta_bonuses(&meta::get_bonuses(&db));
     }
+    drop(db);
 
     let result = world.build_turn_result(Vec::new());
 
@@ -67,6 +92,12 @@ pub fn new_game(seed: Option<String>, class: Option<String>, modifiers: Option<V
     Ok(result)
 }
 
+/// Backgrounds, point budget and name rules for the character creation screen.
+#[tauri::command]
+pub fn get_character_options() -> CreationOptions {
+    backgrounds::creation_options()
+}
+
 #[tauri::command]
 pub fn get_statistics(state: State<'_, AppState>) -> Result<std::collections::HashMap<String, i64>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
@@ -183,9 +214,15 @@ pub fn inspect_entity(entity_id: u32, state: State<'_, AppState>) -> Result<Opti
         crate::engine::combat::effective_defense(entity)
     });
 
+    let name = if entity.item.is_some() {
+        world.identification.display_name(entity)
+    } else {
+        entity.name.clone()
+    };
+
     Ok(Some(EntityDetail {
         id: entity.id,
-        name: entity.name.clone(),
+        name,
         entity_type: if entity.ai.is_some() {
             EntityType::Enemy
         } else if entity.id == world.player_id {
@@ -206,9 +243,57 @@ pub fn inspect_entity(entity_id: u32, state: State<'_, AppState>) -> Result<Opti
             })
             .collect(),
         flavor_text: entity.flavor_text.clone(),
+        affixes: entity
+            .item
+            .as_ref()
+            .filter(|_| world.identification.is_item_identified(entity))
+            .map(|p| p.affixes.iter().map(crate::engine::affixes::describe).collect())
+            .unwrap_or_default(),
     }))
 }
 
+#[tauri::command]
+pub fn inspect_item(source: ItemSource, state: State<'_, AppState>) -> Result<ItemDetail, String> {
+    let world_lock = state.world.lock().map_err(|e| e.to_string())?;
+    let world = world_lock.as_ref().ok_or("No active game")?;
+    world.inspect_item(source)
+}
+
+#[tauri::command]
+pub fn preview_target(source: TargetSource, target: Position, state: State<'_, AppState>) -> Result<TargetPreview, String> {
+    let world_lock = state.world.lock().map_err(|e| e.to_string())?;
+    let world = world_lock.as_ref().ok_or("No active game")?;
+    world.preview_target(&source, target)
+}
+
+#[tauri::command]
+pub fn preview_combat(
+    attacker_id: u32,
+    target_id: u32,
+    ability_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CombatPreview, String> {
+    let world_lock = state.world.lock().map_err(|e| e.to_string())?;
+    let world = world_lock.as_ref().ok_or("No active game")?;
+
+    // Same visibility rule as inspect_entity: no forecasting unseen monsters
+    let player_fov = world
+        .get_entity(world.player_id)
+        .and_then(|p| p.fov.as_ref());
+    for id in [attacker_id, target_id] {
+        let entity = world.get_entity(id).ok_or("Unknown entity")?;
+        let is_visible = id == world.player_id
+            || player_fov
+                .map(|f| f.visible_tiles.contains(&entity.position))
+                .unwrap_or(false);
+        if !is_visible {
+            return Err("Target is not visible".to_string());
+        }
+    }
+
+    world.preview_combat(attacker_id, target_id, ability_id.as_deref())
+}
+
 #[tauri::command]
 pub fn get_run_history(state: State<'_, AppState>) -> Result<Vec<RunSummary>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
@@ -258,7 +343,7 @@ pub fn get_adjacent_shop(state: State<'_, AppState>) -> Result<Option<ShopView>,
                     shop_id: shop_entity.id,
                     name: shop_entity.name.clone(),
                     items: shop.items.iter().map(|item| ShopItemView {
-                        name: item.name.clone(),
+                        name: world.shop_item_name(item),
                         price: item.price,
                         item_type: item.item_type,
                         slot: item.slot,
@@ -271,6 +356,13 @@ pub fn get_adjacent_shop(state: State<'_, AppState>) -> Result<Option<ShopView>,
     Ok(None)
 }
 
+#[tauri::command]
+pub fn get_recipe_book(state: State<'_, AppState>) -> Result<Vec<recipes::RecipeView>, String> {
+    let world_lock = state.world.lock().map_err(|e| e.to_string())?;
+    let world = world_lock.as_ref().ok_or("No active game")?;
+    Ok(world.recipe_views())
+}
+
 #[tauri::command]
 pub fn get_achievements(state: State<'_, AppState>) -> Result<Vec<achievements::AchievementStatus>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
@@ -283,6 +375,26 @@ pub fn get_unlockables(state: State<'_, AppState>) -> Result<Vec<achievements::U
     Ok(achievements::get_all_unlock_statuses(&db))
 }
 
+#[tauri::command]
+pub fn get_meta_progress(state: State<'_, AppState>) -> Result<meta::MetaStatus, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(meta::get_status(&db))
+}
+
+#[tauri::command]
+pub fn buy_meta_upgrade(upgrade_id: String, state: State<'_, AppState>) -> Result<meta::MetaStatus, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    meta::buy_upgrade(&db, &upgrade_id)?;
+    Ok(meta::get_status(&db))
+}
+
+#[tauri::command]
+pub fn reset_meta_progress(state: State<'_, AppState>) -> Result<meta::MetaStatus, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    meta::reset_upgrades(&db)?;
+    Ok(meta::get_status(&db))
+}
+
 #[tauri::command]
 pub fn check_ollama(state: State<'_, AppState>) -> Result<OllamaStatus, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
@@ -1,10 +1,22 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
 use super::entity::*;
+use super::subclasses;
+
+/// Highest rank an ability can reach.
+pub const MAX_RANK: u32 = 5;
 
 #[derive(Debug, Clone)]
 pub struct Ability {
     pub id: String,
     pub name: String,
     pub mana_cost: i32,
+    /// Turns before the ability (or its next charge) is ready again.
+    pub cooldown: u32,
+    /// Uses stored up between cooldowns; None casts once per cooldown.
+    pub charges: Option<u32>,
     pub class: PlayerClass,
     pub targeting: AbilityTargeting,
     pub effect: AbilityEffect,
@@ -21,7 +33,7 @@ pub enum AbilityTargeting {
 
 #[derive(Debug, Clone)]
 pub enum AbilityEffect {
-    Damage { amount: i32 },
+    Damage { amount: i32, damage_type: DamageType },
     StatusSelf { status: StatusType, duration: u32 },
     StatusTarget { status: StatusType, duration: u32 },
     Move { distance: i32 },
@@ -29,6 +41,135 @@ pub enum AbilityEffect {
     Shield { absorb: i32 },
     DamageAdjacent { amount: i32 },
     PoisonNextAttack { damage: i32, duration: u32 },
+    Heal { amount: i32 },
+    /// Holy damage and a stun for every undead within `radius`.
+    TurnUndead { radius: i32, damage: i32, duration: u32 },
+    /// Summon the Ranger's companion, or mend it if it still lives.
+    CallCompanion,
+    /// Raise a corpse as an ally for `duration` turns.
+    RaiseDead { duration: u32 },
+    /// Lightning that leaps from the target to up to `jumps` more foes nearby.
+    ChainLightning { damage: i32, jumps: u32 },
+    /// Call up an allied copy of an enemy for `duration` turns.
+    Summon { creature: &'static str, duration: u32 },
+    /// Drag the target to the caster's side.
+    Pull,
+    /// Damage every foe within `radius` of the caster each turn for `duration` turns.
+    Aura { damage: i32, damage_type: DamageType, radius: i32, duration: u32 },
+}
+
+impl Ability {
+    /// Sorcery rather than a martial technique: the caster classes' abilities.
+    pub fn is_spell(&self) -> bool {
+        matches!(self.class, PlayerClass::Mage | PlayerClass::Cleric | PlayerClass::Necromancer)
+    }
+
+    /// This ability at `rank` (1 = as listed). Each rank past the first adds
+    /// a quarter to damage, healing and shields and a turn to durations;
+    /// areas widen by a tile at ranks 3 and 5.
+    pub fn at_rank(mut self, rank: u32) -> Ability {
+        let extra = rank.clamp(1, MAX_RANK) - 1;
+        let scale = |n: i32| n + n * extra as i32 / 4;
+        let turns = |d: u32| d + extra;
+        let wider = (extra / 2) as i32;
+
+        if let AbilityTargeting::Targeted { ref mut radius, .. } = self.targeting {
+            if *radius > 0 {
+                *radius += wider;
+            }
+        }
+        self.effect = match self.effect {
+            AbilityEffect::Damage { amount, damage_type } => AbilityEffect::Damage { amount: scale(amount), damage_type },
+            AbilityEffect::StatusSelf { status, duration } => AbilityEffect::StatusSelf { status, duration: turns(duration) },
+            AbilityEffect::StatusTarget { status, duration } => AbilityEffect::StatusTarget { status, duration: turns(duration) },
+            AbilityEffect::Shield { absorb } => AbilityEffect::Shield { absorb: scale(absorb) },
+            AbilityEffect::DamageAdjacent { amount } => AbilityEffect::DamageAdjacent { amount: scale(amount) },
+            AbilityEffect::PoisonNextAttack { damage, duration } => {
+                AbilityEffect::PoisonNextAttack { damage: scale(damage), duration: turns(duration) }
+            }
+            AbilityEffect::Heal { amount } => AbilityEffect::Heal { amount: scale(amount) },
+            AbilityEffect::TurnUndead { radius, damage, duration } => {
+                AbilityEffect::TurnUndead { radius: radius + wider, damage: scale(damage), duration: turns(duration) }
+            }
+            AbilityEffect::RaiseDead { duration } => AbilityEffect::RaiseDead { duration: duration + 5 * extra },
+            AbilityEffect::ChainLightning { damage, jumps } => {
+                AbilityEffect::ChainLightning { damage: scale(damage), jumps: jumps + wider as u32 }
+            }
+            AbilityEffect::Summon { creature, duration } => AbilityEffect::Summon { creature, duration: duration + 5 * extra },
+            AbilityEffect::Aura { damage, damage_type, radius, duration } => AbilityEffect::Aura {
+                damage: scale(damage),
+                damage_type,
+                radius: radius + wider,
+                duration: turns(duration),
+            },
+            effect => effect,
+        };
+        self
+    }
+
+    pub fn max_charges(&self) -> u32 {
+        self.charges.unwrap_or(1)
+    }
+
+    pub fn charges_left(&self, cooldown: &AbilityCooldown) -> u32 {
+        self.max_charges().saturating_sub(cooldown.spent)
+    }
+}
+
+/// Rank every ability has at a character level, before talents.
+pub fn rank_for_level(level: u32) -> u32 {
+    1 + level / 5
+}
+
+/// Cooldown bookkeeping for one ability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbilityCooldown {
+    /// Turns until the next charge comes back.
+    pub remaining: u32,
+    /// Charges used and not yet recovered.
+    pub spent: u32,
+}
+
+impl AbilityCooldown {
+    /// Use a charge, starting the cooldown unless one is already running.
+    pub fn spend(&mut self, cooldown: u32) {
+        if cooldown == 0 {
+            return;
+        }
+        self.spent += 1;
+        if self.remaining == 0 {
+            self.remaining = cooldown;
+        }
+    }
+
+    /// Advance one turn. Finishing a cooldown restores a charge and starts
+    /// the next one if more are spent.
+    pub fn tick(&mut self, cooldown: u32) {
+        if self.remaining == 0 {
+            return;
+        }
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.spent = self.spent.saturating_sub(1);
+            if self.spent > 0 {
+                self.remaining = cooldown;
+            }
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.remaining == 0 && self.spent == 0
+    }
+}
+
+/// A lingering aura around the player, such as Consecration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveAura {
+    pub name: String,
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub radius: i32,
+    pub turns_left: u32,
 }
 
 pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
@@ -38,6 +179,8 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "shield_bash".to_string(),
                 name: "Shield Bash".to_string(),
                 mana_cost: 15,
+                cooldown: 3,
+                charges: None,
                 class: PlayerClass::Warrior,
                 targeting: AbilityTargeting::Adjacent,
                 effect: AbilityEffect::StatusTarget { status: StatusType::Stunned, duration: 2 },
@@ -47,6 +190,8 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "war_cry".to_string(),
                 name: "War Cry".to_string(),
                 mana_cost: 20,
+                cooldown: 12,
+                charges: None,
                 class: PlayerClass::Warrior,
                 targeting: AbilityTargeting::SelfOnly,
                 effect: AbilityEffect::StatusSelf { status: StatusType::Strengthened, duration: 10 },
@@ -56,6 +201,8 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "whirlwind".to_string(),
                 name: "Whirlwind".to_string(),
                 mana_cost: 25,
+                cooldown: 4,
+                charges: None,
                 class: PlayerClass::Warrior,
                 targeting: AbilityTargeting::SelfOnly,
                 effect: AbilityEffect::DamageAdjacent { amount: 8 },
@@ -67,6 +214,8 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "smoke_bomb".to_string(),
                 name: "Smoke Bomb".to_string(),
                 mana_cost: 15,
+                cooldown: 10,
+                charges: None,
                 class: PlayerClass::Rogue,
                 targeting: AbilityTargeting::SelfOnly,
                 effect: AbilityEffect::StatusSelf { status: StatusType::Invisible, duration: 3 },
@@ -76,6 +225,8 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "dash".to_string(),
                 name: "Dash".to_string(),
                 mana_cost: 10,
+                cooldown: 3,
+                charges: Some(2),
                 class: PlayerClass::Rogue,
                 targeting: AbilityTargeting::Direction,
                 effect: AbilityEffect::Move { distance: 3 },
@@ -85,6 +236,8 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "poison_strike".to_string(),
                 name: "Poison Strike".to_string(),
                 mana_cost: 20,
+                cooldown: 6,
+                charges: None,
                 class: PlayerClass::Rogue,
                 targeting: AbilityTargeting::SelfOnly,
                 effect: AbilityEffect::PoisonNextAttack { damage: 3, duration: 5 },
@@ -96,15 +249,19 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "fireball".to_string(),
                 name: "Fireball".to_string(),
                 mana_cost: 20,
+                cooldown: 4,
+                charges: None,
                 class: PlayerClass::Mage,
                 targeting: AbilityTargeting::Targeted { range: 6, radius: 2 },
-                effect: AbilityEffect::Damage { amount: 12 },
+                effect: AbilityEffect::Damage { amount: 12, damage_type: DamageType::Fire },
                 description: "Launch a fireball that explodes in a 2-tile radius.".to_string(),
             },
             Ability {
                 id: "frost_bolt".to_string(),
                 name: "Frost Bolt".to_string(),
                 mana_cost: 15,
+                cooldown: 3,
+                charges: Some(2),
                 class: PlayerClass::Mage,
                 targeting: AbilityTargeting::Targeted { range: 8, radius: 0 },
                 effect: AbilityEffect::StatusTarget { status: StatusType::Slowed, duration: 3 },
@@ -114,6 +271,8 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "blink".to_string(),
                 name: "Blink".to_string(),
                 mana_cost: 25,
+                cooldown: 6,
+                charges: None,
                 class: PlayerClass::Mage,
                 targeting: AbilityTargeting::Targeted { range: 5, radius: 0 },
                 effect: AbilityEffect::Teleport { range: 5 },
@@ -123,27 +282,351 @@ pub fn get_abilities(class: PlayerClass) -> Vec<Ability> {
                 id: "arcane_shield".to_string(),
                 name: "Arcane Shield".to_string(),
                 mana_cost: 20,
+                cooldown: 10,
+                charges: None,
                 class: PlayerClass::Mage,
                 targeting: AbilityTargeting::SelfOnly,
                 effect: AbilityEffect::Shield { absorb: 20 },
                 description: "Create a magical shield that absorbs 20 damage.".to_string(),
             },
         ],
+        PlayerClass::Ranger => vec![
+            Ability {
+                id: "aimed_shot".to_string(),
+                name: "Aimed Shot".to_string(),
+                mana_cost: 15,
+                cooldown: 2,
+                charges: None,
+                class: PlayerClass::Ranger,
+                targeting: AbilityTargeting::Targeted { range: 8, radius: 0 },
+                effect: AbilityEffect::Damage { amount: 10, damage_type: DamageType::Physical },
+                description: "A careful shot for 10 damage at up to 8 tiles.".to_string(),
+            },
+            Ability {
+                id: "call_companion".to_string(),
+                name: "Call Companion".to_string(),
+                mana_cost: 20,
+                cooldown: 15,
+                charges: None,
+                class: PlayerClass::Ranger,
+                targeting: AbilityTargeting::SelfOnly,
+                effect: AbilityEffect::CallCompanion,
+                description: "Call your wolf to your side, fully healed, or a new one if it has fallen.".to_string(),
+            },
+            Ability {
+                id: "snare".to_string(),
+                name: "Snare".to_string(),
+                mana_cost: 10,
+                cooldown: 4,
+                charges: Some(2),
+                class: PlayerClass::Ranger,
+                targeting: AbilityTargeting::Targeted { range: 5, radius: 0 },
+                effect: AbilityEffect::StatusTarget { status: StatusType::Slowed, duration: 4 },
+                description: "Entangle a target within 5 tiles, slowing it for 4 turns.".to_string(),
+            },
+        ],
+        PlayerClass::Cleric => vec![
+            Ability {
+                id: "heal".to_string(),
+                name: "Heal".to_string(),
+                mana_cost: 20,
+                cooldown: 5,
+                charges: Some(2),
+                class: PlayerClass::Cleric,
+                targeting: AbilityTargeting::SelfOnly,
+                effect: AbilityEffect::Heal { amount: 20 },
+                description: "Restore 20 HP.".to_string(),
+            },
+            Ability {
+                id: "turn_undead".to_string(),
+                name: "Turn Undead".to_string(),
+                mana_cost: 25,
+                cooldown: 8,
+                charges: None,
+                class: PlayerClass::Cleric,
+                targeting: AbilityTargeting::SelfOnly,
+                effect: AbilityEffect::TurnUndead { radius: 4, damage: 10, duration: 3 },
+                description: "Sear undead within 4 tiles for 10 holy damage and stun them for 3 turns.".to_string(),
+            },
+            Ability {
+                id: "smite".to_string(),
+                name: "Smite".to_string(),
+                mana_cost: 15,
+                cooldown: 2,
+                charges: None,
+                class: PlayerClass::Cleric,
+                targeting: AbilityTargeting::Targeted { range: 5, radius: 0 },
+                effect: AbilityEffect::Damage { amount: 10, damage_type: DamageType::Holy },
+                description: "Call down 10 holy damage on a target within 5 tiles.".to_string(),
+            },
+        ],
+        PlayerClass::Necromancer => vec![
+            Ability {
+                id: "raise_dead".to_string(),
+                name: "Raise Dead".to_string(),
+                mana_cost: 20,
+                cooldown: 6,
+                charges: None,
+                class: PlayerClass::Necromancer,
+                targeting: AbilityTargeting::Targeted { range: 5, radius: 0 },
+                effect: AbilityEffect::RaiseDead { duration: 20 },
+                description: "Raise a corpse within 5 tiles to fight for you for 20 turns.".to_string(),
+            },
+            Ability {
+                id: "drain_life".to_string(),
+                name: "Drain Life".to_string(),
+                mana_cost: 15,
+                cooldown: 2,
+                charges: None,
+                class: PlayerClass::Necromancer,
+                targeting: AbilityTargeting::Targeted { range: 6, radius: 0 },
+                effect: AbilityEffect::Damage { amount: 9, damage_type: DamageType::Necrotic },
+                description: "Wither a target within 6 tiles for 9 necrotic damage.".to_string(),
+            },
+            Ability {
+                id: "bone_armor".to_string(),
+                name: "Bone Armor".to_string(),
+                mana_cost: 20,
+                cooldown: 10,
+                charges: None,
+                class: PlayerClass::Necromancer,
+                targeting: AbilityTargeting::SelfOnly,
+                effect: AbilityEffect::Shield { absorb: 15 },
+                description: "Wrap yourself in bone that absorbs 15 damage.".to_string(),
+            },
+        ],
+    }
+}
+
+/// Abilities a class only gains by learning the matching talent.
+pub fn talent_abilities(class: PlayerClass) -> Vec<Ability> {
+    match class {
+        PlayerClass::Warrior => vec![Ability {
+            id: "battle_focus".to_string(),
+            name: "Battle Focus".to_string(),
+            mana_cost: 20,
+            cooldown: 12,
+            charges: None,
+            class: PlayerClass::Warrior,
+            targeting: AbilityTargeting::SelfOnly,
+            effect: AbilityEffect::StatusSelf { status: StatusType::Hasted, duration: 5 },
+            description: "Gain Hasted for 5 turns.".to_string(),
+        }, Ability {
+            id: "chain_hook".to_string(),
+            name: "Chain Hook".to_string(),
+            mana_cost: 15,
+            cooldown: 5,
+            charges: None,
+            class: PlayerClass::Warrior,
+            targeting: AbilityTargeting::Targeted { range: 5, radius: 0 },
+            effect: AbilityEffect::Pull,
+            description: "Drag a foe within 5 tiles to your side.".to_string(),
+        }],
+        PlayerClass::Rogue => vec![Ability {
+            id: "crippling_throw".to_string(),
+            name: "Crippling Throw".to_string(),
+            mana_cost: 15,
+            cooldown: 4,
+            charges: None,
+            class: PlayerClass::Rogue,
+            targeting: AbilityTargeting::Targeted { range: 5, radius: 0 },
+            effect: AbilityEffect::StatusTarget { status: StatusType::Slowed, duration: 4 },
+            description: "Slow a target within 5 tiles for 4 turns.".to_string(),
+        }],
+        PlayerClass::Mage => vec![Ability {
+            id: "chain_spark".to_string(),
+            name: "Chain Spark".to_string(),
+            mana_cost: 25,
+            cooldown: 5,
+            charges: None,
+            class: PlayerClass::Mage,
+            targeting: AbilityTargeting::Targeted { range: 6, radius: 0 },
+            effect: AbilityEffect::ChainLightning { damage: 8, jumps: 3 },
+            description: "Lightning leaps from a target to up to 3 more foes nearby.".to_string(),
+        }],
+        PlayerClass::Ranger => vec![Ability {
+            id: "volley".to_string(),
+            name: "Volley".to_string(),
+            mana_cost: 25,
+            cooldown: 5,
+            charges: None,
+            class: PlayerClass::Ranger,
+            targeting: AbilityTargeting::Targeted { range: 7, radius: 1 },
+            effect: AbilityEffect::Damage { amount: 7, damage_type: DamageType::Physical },
+            description: "Rain arrows on a target and everything beside it.".to_string(),
+        }],
+        PlayerClass::Cleric => vec![Ability {
+            id: "holy_nova".to_string(),
+            name: "Holy Nova".to_string(),
+            mana_cost: 30,
+            cooldown: 6,
+            charges: None,
+            class: PlayerClass::Cleric,
+            targeting: AbilityTargeting::Targeted { range: 0, radius: 2 },
+            effect: AbilityEffect::Damage { amount: 8, damage_type: DamageType::Holy },
+            description: "Radiant light burns every foe within 2 tiles.".to_string(),
+        }, Ability {
+            id: "consecration".to_string(),
+            name: "Consecration".to_string(),
+            mana_cost: 25,
+            cooldown: 15,
+            charges: None,
+            class: PlayerClass::Cleric,
+            targeting: AbilityTargeting::SelfOnly,
+            effect: AbilityEffect::Aura { damage: 3, damage_type: DamageType::Holy, radius: 2, duration: 8 },
+            description: "Hallow the ground: foes within 2 tiles take 3 holy damage a turn for 8 turns.".to_string(),
+        }],
+        PlayerClass::Necromancer => vec![Ability {
+            id: "bone_spear".to_string(),
+            name: "Bone Spear".to_string(),
+            mana_cost: 25,
+            cooldown: 4,
+            charges: None,
+            class: PlayerClass::Necromancer,
+            targeting: AbilityTargeting::Targeted { range: 7, radius: 0 },
+            effect: AbilityEffect::Damage { amount: 16, damage_type: DamageType::Physical },
+            description: "Hurl a spear of bone for 16 damage.".to_string(),
+        }, Ability {
+            id: "summon_skeleton".to_string(),
+            name: "Summon Skeleton".to_string(),
+            mana_cost: 25,
+            cooldown: 12,
+            charges: None,
+            class: PlayerClass::Necromancer,
+            targeting: AbilityTargeting::SelfOnly,
+            effect: AbilityEffect::Summon { creature: "Skeleton", duration: 15 },
+            description: "Call up a skeleton to fight for you for 15 turns.".to_string(),
+        }],
     }
 }
 
+/// The signature ability a specialization adds to the kit.
+pub fn subclass_ability(subclass: Subclass) -> Ability {
+    let (class, id, name, mana_cost, cooldown, charges, targeting, effect, description) = match subclass {
+        Subclass::Berserker => (
+            PlayerClass::Warrior, "reckless_swing", "Reckless Swing", 10, 2, None,
+            AbilityTargeting::Adjacent,
+            AbilityEffect::Damage { amount: 14, damage_type: DamageType::Physical },
+            "A wild blow dealing 14 damage to an adjacent enemy.",
+        ),
+        Subclass::Guardian => (
+            PlayerClass::Warrior, "bulwark", "Bulwark", 20, 12, None,
+            AbilityTargeting::SelfOnly,
+            AbilityEffect::Shield { absorb: 25 },
+            "Brace behind your guard, absorbing 25 damage.",
+        ),
+        Subclass::Assassin => (
+            PlayerClass::Rogue, "assassinate", "Assassinate", 20, 6, None,
+            AbilityTargeting::Adjacent,
+            AbilityEffect::Damage { amount: 20, damage_type: DamageType::Physical },
+            "Strike a vital spot on an adjacent enemy for 20 damage.",
+        ),
+        Subclass::Trickster => (
+            PlayerClass::Rogue, "shadow_step", "Shadow Step", 15, 5, Some(2),
+            AbilityTargeting::Targeted { range: 5, radius: 0 },
+            AbilityEffect::Teleport { range: 5 },
+            "Step through the shadows to a visible tile within 5. Holds 2 charges.",
+        ),
+        Subclass::Pyromancer => (
+            PlayerClass::Mage, "immolate", "Immolate", 20, 3, None,
+            AbilityTargeting::Targeted { range: 6, radius: 0 },
+            AbilityEffect::Damage { amount: 16, damage_type: DamageType::Fire },
+            "Engulf a target within 6 tiles in flame for 16 damage.",
+        ),
+        Subclass::Cryomancer => (
+            PlayerClass::Mage, "frost_nova", "Frost Nova", 25, 8, None,
+            AbilityTargeting::Targeted { range: 0, radius: 2 },
+            AbilityEffect::StatusTarget { status: StatusType::Stunned, duration: 3 },
+            "Freeze every foe within 2 tiles in place for 3 turns.",
+        ),
+        Subclass::Beastmaster => (
+            PlayerClass::Ranger, "call_pack", "Call the Pack", 25, 12, None,
+            AbilityTargeting::SelfOnly,
+            AbilityEffect::Summon { creature: "Giant Spider", duration: 20 },
+            "Call a giant spider to hunt beside you for 20 turns.",
+        ),
+        Subclass::Sharpshooter => (
+            PlayerClass::Ranger, "piercing_shot", "Piercing Shot", 15, 3, None,
+            AbilityTargeting::Targeted { range: 8, radius: 0 },
+            AbilityEffect::Damage { amount: 18, damage_type: DamageType::Physical },
+            "A shot at a target within 8 tiles for 18 damage.",
+        ),
+        Subclass::Crusader => (
+            PlayerClass::Cleric, "holy_strike", "Holy Strike", 15, 2, None,
+            AbilityTargeting::Adjacent,
+            AbilityEffect::Damage { amount: 18, damage_type: DamageType::Holy },
+            "Smash an adjacent enemy with radiant force for 18 damage.",
+        ),
+        Subclass::Oracle => (
+            PlayerClass::Cleric, "greater_heal", "Greater Heal", 30, 8, None,
+            AbilityTargeting::SelfOnly,
+            AbilityEffect::Heal { amount: 40 },
+            "Restore 40 HP.",
+        ),
+        Subclass::Lich => (
+            PlayerClass::Necromancer, "soul_rend", "Soul Rend", 20, 4, None,
+            AbilityTargeting::Targeted { range: 6, radius: 0 },
+            AbilityEffect::Damage { amount: 18, damage_type: DamageType::Necrotic },
+            "Tear at the soul of a target within 6 tiles for 18 damage.",
+        ),
+        Subclass::Bonelord => (
+            PlayerClass::Necromancer, "bone_storm", "Bone Storm", 25, 6, None,
+            AbilityTargeting::SelfOnly,
+            AbilityEffect::DamageAdjacent { amount: 12 },
+            "Shards of bone deal 12 damage to every adjacent enemy.",
+        ),
+    };
+    Ability {
+        id: id.to_string(),
+        name: name.to_string(),
+        mana_cost,
+        cooldown,
+        charges,
+        class,
+        targeting,
+        effect,
+        description: description.to_string(),
+    }
+}
+
+/// The class's starting kit, reshaped by its specialization, plus the
+/// talent abilities in `unlocked`.
+pub fn known_abilities(class: PlayerClass, subclass: Option<Subclass>, unlocked: &[&str]) -> Vec<Ability> {
+    let mut abilities = get_abilities(class);
+    if let Some(subclass) = subclass {
+        let def = subclasses::get_def(subclass);
+        abilities.retain(|a| Some(a.id.as_str()) != def.replaces);
+        abilities.push(subclass_ability(subclass));
+    }
+    abilities.extend(talent_abilities(class).into_iter().filter(|a| unlocked.contains(&a.id.as_str())));
+    abilities
+}
+
 pub fn get_ability(class: PlayerClass, ability_id: &str) -> Option<Ability> {
     get_abilities(class).into_iter().find(|a| a.id == ability_id)
 }
 
 pub fn to_ability_views(class: PlayerClass) -> Vec<AbilityView> {
-    get_abilities(class)
+    to_views(&get_abilities(class), 1, &HashMap::new())
+}
+
+pub fn to_views(abilities: &[Ability], rank: u32, cooldowns: &HashMap<String, AbilityCooldown>) -> Vec<AbilityView> {
+    abilities
         .iter()
-        .map(|a| AbilityView {
-            id: a.id.clone(),
-            name: a.name.clone(),
-            mana_cost: a.mana_cost,
-            description: a.description.clone(),
+        .map(|a| {
+            let cooldown = cooldowns.get(&a.id).copied().unwrap_or_default();
+            AbilityView {
+                id: a.id.clone(),
+                name: a.name.clone(),
+                mana_cost: a.mana_cost,
+                description: a.description.clone(),
+                rank,
+                cooldown: a.cooldown,
+                cooldown_remaining: cooldown.remaining,
+                charges: a.charges.map(|_| a.charges_left(&cooldown)),
+                max_charges: a.charges,
+                ready: a.charges_left(&cooldown) > 0,
+            }
         })
         .collect()
 }
@@ -173,6 +656,16 @@ mod tests {
         assert_eq!(abilities[0].id, "fireball");
     }
 
+    #[test]
+    fn new_classes_have_kits() {
+        for class in [PlayerClass::Ranger, PlayerClass::Cleric, PlayerClass::Necromancer] {
+            let abilities = get_abilities(class);
+            assert_eq!(abilities.len(), 3);
+            assert!(abilities.iter().all(|a| a.class == class));
+        }
+        assert!(get_ability(PlayerClass::Necromancer, "raise_dead").is_some());
+    }
+
     #[test]
     fn get_ability_by_id() {
         let a = get_ability(PlayerClass::Mage, "fireball");
@@ -183,6 +676,58 @@ mod tests {
         assert!(none.is_none());
     }
 
+    #[test]
+    fn talent_abilities_join_the_kit_once_unlocked() {
+        assert_eq!(known_abilities(PlayerClass::Mage, None, &[]).len(), 4);
+        let kit = known_abilities(PlayerClass::Mage, None, &["chain_spark"]);
+        assert_eq!(kit.len(), 5);
+        assert_eq!(kit[4].id, "chain_spark");
+        assert!(get_abilities(PlayerClass::Mage).iter().all(|a| a.id != "chain_spark"));
+    }
+
+    #[test]
+    fn specializations_reshape_the_kit() {
+        let kit = known_abilities(PlayerClass::Mage, Some(Subclass::Cryomancer), &[]);
+        assert_eq!(kit.len(), 4);
+        assert!(kit.iter().all(|a| a.id != "fireball"));
+        assert!(kit.iter().any(|a| a.id == "frost_nova"));
+
+        let kit = known_abilities(PlayerClass::Warrior, Some(Subclass::Guardian), &[]);
+        assert_eq!(kit.len(), 4);
+        assert_eq!(kit[3].id, "bulwark");
+    }
+
+    #[test]
+    fn charges_come_back_one_cooldown_at_a_time() {
+        let snare = get_ability(PlayerClass::Ranger, "snare").unwrap();
+        let mut cd = AbilityCooldown::default();
+        cd.spend(snare.cooldown);
+        cd.spend(snare.cooldown);
+        assert_eq!(snare.charges_left(&cd), 0);
+        for _ in 0..snare.cooldown {
+            cd.tick(snare.cooldown);
+        }
+        assert_eq!(snare.charges_left(&cd), 1);
+        assert_eq!(cd.remaining, snare.cooldown);
+        for _ in 0..snare.cooldown {
+            cd.tick(snare.cooldown);
+        }
+        assert!(cd.is_idle());
+    }
+
+    #[test]
+    fn ranks_strengthen_effects() {
+        let fireball = get_ability(PlayerClass::Mage, "fireball").unwrap().at_rank(3);
+        assert!(matches!(fireball.effect, AbilityEffect::Damage { amount: 18, .. }));
+        assert!(matches!(fireball.targeting, AbilityTargeting::Targeted { radius: 3, .. }));
+        // Single-target abilities stay single-target
+        let frost = get_ability(PlayerClass::Mage, "frost_bolt").unwrap().at_rank(5);
+        assert!(matches!(frost.targeting, AbilityTargeting::Targeted { radius: 0, .. }));
+        assert!(matches!(frost.effect, AbilityEffect::StatusTarget { duration: 7, .. }));
+        assert_eq!(rank_for_level(1), 1);
+        assert_eq!(rank_for_level(10), 3);
+    }
+
     #[test]
     fn ability_views() {
         let views = to_ability_views(PlayerClass::Warrior);
@@ -0,0 +1,197 @@
+use rand::Rng;
+
+use super::entity::*;
+
+/// Speed per Haste tier.
+const HASTE_PER_TIER: i32 = 10;
+/// Crit chance per Keen tier.
+const KEEN_PER_TIER: f32 = 0.05;
+/// Defense per Warding tier.
+const WARDING_PER_TIER: i32 = 1;
+/// Percent of damage dealt healed per Vampiric tier.
+const VAMPIRIC_PERCENT_PER_TIER: i32 = 15;
+/// Gold added to an item's value per affix tier.
+const PRICE_PER_TIER: u32 = 10;
+
+impl AffixKind {
+    /// Whether this affix can roll on gear worn in `slot`. Offensive affixes
+    /// only appear on weapons, Warding only on worn protection.
+    pub fn fits_slot(&self, slot: EquipSlot) -> bool {
+        match self {
+            AffixKind::Haste => true,
+            AffixKind::Vampiric | AffixKind::Keen | AffixKind::Flaming => slot == EquipSlot::MainHand,
+            AffixKind::Warding => slot != EquipSlot::MainHand,
+        }
+    }
+
+    fn is_prefix(&self) -> bool {
+        matches!(self, AffixKind::Keen | AffixKind::Flaming | AffixKind::Vampiric)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AffixKind::Haste => "of Haste",
+            AffixKind::Vampiric => "Vampiric",
+            AffixKind::Keen => "Keen",
+            AffixKind::Flaming => "Flaming",
+            AffixKind::Warding => "of Warding",
+        }
+    }
+}
+
+const ALL_AFFIXES: [AffixKind; 5] = [
+    AffixKind::Haste,
+    AffixKind::Vampiric,
+    AffixKind::Keen,
+    AffixKind::Flaming,
+    AffixKind::Warding,
+];
+
+/// Roll affixes for a piece of equipment found on `floor`. Magic items
+/// (one affix) become more common with depth; from floor 4 some are rare
+/// (two affixes). Tiers climb to 2 from floor 4 and 3 from floor 8.
+pub fn roll_affixes(slot: EquipSlot, floor: u32, rng: &mut impl Rng) -> Vec<Affix> {
+    let magic_chance = (floor as f32 * 0.04).min(0.4);
+    if rng.gen::<f32>() >= magic_chance {
+        return Vec::new();
+    }
+    let count = if floor >= 4 && rng.gen::<f32>() < 0.25 { 2 } else { 1 };
+
+    let mut pool: Vec<AffixKind> = ALL_AFFIXES.iter().copied().filter(|k| k.fits_slot(slot)).collect();
+    let mut affixes = Vec::new();
+    for _ in 0..count {
+        if pool.is_empty() {
+            break;
+        }
+        let kind = pool.swap_remove(rng.gen_range(0..pool.len()));
+        affixes.push(Affix { kind, tier: roll_tier(floor, rng) });
+    }
+    affixes
+}
+
+fn roll_tier(floor: u32, rng: &mut impl Rng) -> u8 {
+    let roll = rng.gen::<f32>();
+    if floor >= 8 && roll < 0.3 {
+        3
+    } else if floor >= 4 && roll < 0.5 {
+        2
+    } else {
+        1
+    }
+}
+
+/// "Keen Short Sword of Haste": prefixes before the base name, suffixes after.
+pub fn affixed_name(base: &str, affixes: &[Affix]) -> String {
+    let mut name = String::new();
+    for affix in affixes.iter().filter(|a| a.kind.is_prefix()) {
+        name.push_str(affix.kind.name());
+        name.push(' ');
+    }
+    name.push_str(base);
+    for affix in affixes.iter().filter(|a| !a.kind.is_prefix()) {
+        name.push(' ');
+        name.push_str(affix.kind.name());
+    }
+    name
+}
+
+pub fn describe(affix: &Affix) -> String {
+    let tier = affix.tier as i32;
+    match affix.kind {
+        AffixKind::Haste => format!("Haste: +{} speed", HASTE_PER_TIER * tier),
+        AffixKind::Vampiric => format!("Vampiric: heals {}% of damage dealt", VAMPIRIC_PERCENT_PER_TIER * tier),
+        AffixKind::Keen => format!("Keen: +{:.0}% crit chance", KEEN_PER_TIER * tier as f32 * 100.0),
+        AffixKind::Flaming => format!("Flaming: burns for {} per turn", flaming_burn_damage(tier)),
+        AffixKind::Warding => format!("Warding: +{} defense", WARDING_PER_TIER * tier),
+    }
+}
+
+pub fn price_bonus(affixes: &[Affix]) -> u32 {
+    affixes.iter().map(|a| a.tier as u32 * PRICE_PER_TIER).sum()
+}
+
+/// Sum of tiers of `kind` across everything the entity has equipped.
+/// Broken gear contributes nothing.
+pub fn equipped_tiers(entity: &Entity, kind: AffixKind) -> i32 {
+    let (equipment, inventory) = match (&entity.equipment, &entity.inventory) {
+        (Some(e), Some(inv)) => (e, inv),
+        _ => return 0,
+    };
+    [equipment.main_hand, equipment.off_hand, equipment.head, equipment.body, equipment.ring, equipment.amulet]
+        .iter()
+        .flatten()
+        .filter_map(|id| inventory.items.iter().find(|i| i.id == *id))
+        .filter_map(|item| item.item.as_ref())
+        .filter(|props| !props.is_broken())
+        .flat_map(|props| props.affixes.iter())
+        .filter(|a| a.kind == kind)
+        .map(|a| a.tier as i32)
+        .sum()
+}
+
+pub fn speed_bonus(entity: &Entity) -> i32 {
+    equipped_tiers(entity, AffixKind::Haste) * HASTE_PER_TIER
+}
+
+pub fn defense_bonus(entity: &Entity) -> i32 {
+    equipped_tiers(entity, AffixKind::Warding) * WARDING_PER_TIER
+}
+
+pub fn crit_bonus(entity: &Entity) -> f32 {
+    equipped_tiers(entity, AffixKind::Keen) as f32 * KEEN_PER_TIER
+}
+
+/// HP the attacker regains after dealing `damage` with Vampiric gear.
+pub fn vampiric_heal(entity: &Entity, damage: i32) -> i32 {
+    let tiers = equipped_tiers(entity, AffixKind::Vampiric);
+    if tiers == 0 || damage <= 0 {
+        return 0;
+    }
+    (damage * tiers * VAMPIRIC_PERCENT_PER_TIER / 100).max(1)
+}
+
+/// Burn inflicted on hit by Flaming weapons, if any.
+pub fn flaming_on_hit(entity: &Entity) -> Option<OnHitEffect> {
+    let tiers = equipped_tiers(entity, AffixKind::Flaming);
+    (tiers > 0).then(|| OnHitEffect::Burn {
+        damage: flaming_burn_damage(tiers),
+        duration: 3,
+    })
+}
+
+fn flaming_burn_damage(tiers: i32) -> i32 {
+    tiers + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn affixes_respect_slot_and_depth() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut magic = 0;
+        for _ in 0..500 {
+            let affixes = roll_affixes(EquipSlot::Body, 10, &mut rng);
+            if !affixes.is_empty() {
+                magic += 1;
+            }
+            assert!(affixes.iter().all(|a| a.kind.fits_slot(EquipSlot::Body)));
+            assert!(affixes.iter().all(|a| (1..=3).contains(&a.tier)));
+        }
+        assert!(magic > 100, "Deep floors should roll plenty of magic items");
+        assert!((0..100).all(|_| roll_affixes(EquipSlot::MainHand, 0, &mut rng).is_empty()));
+    }
+
+    #[test]
+    fn affixed_names_place_prefixes_and_suffixes() {
+        let affixes = [
+            Affix { kind: AffixKind::Haste, tier: 1 },
+            Affix { kind: AffixKind::Keen, tier: 2 },
+        ];
+        assert_eq!(affixed_name("Short Sword", &affixes), "Keen Short Sword of Haste");
+        assert_eq!(price_bonus(&affixes), 30);
+    }
+}
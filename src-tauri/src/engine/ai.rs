@@ -1,6 +1,8 @@
+use super::bosses::{BossAbility, BossEncounter};
 use super::entity::*;
 use super::map::Map;
 use super::pathfinding::{DijkstraMap, has_line_of_sight};
+use super::scheduler::ActionKind;
 
 /// Action an AI entity decides to take on its turn.
 #[derive(Debug, Clone)]
@@ -11,16 +13,41 @@ pub enum AIAction {
     MoveAway(Position),
     MoveRandom,
     Wait,
-    /// Goblin King: summon minions. Bool = true means summon archers (Phase 2).
-    BossSummon { summon_archers: bool },
-    /// Troll Warlord: charge to adjacent tile and attack with 2x damage. Bool = stun (Phase 2).
+    /// Summon 1..=max_count minions of the named enemy template.
+    BossSummon { minion: String, max_count: u32 },
+    /// Charge to adjacent tile and attack with 2x damage. Bool = stun.
     BossCharge { stun: bool },
-    /// The Lich: teleport away from player and leave fire at old position.
+    /// Teleport away from player and leave fire at old position.
     BossTeleport,
-    /// The Lich Phase 2: ranged frost bolt attack.
+    /// Ranged frost bolt attack.
     BossFrostBolt,
+    /// Pick up the item lying on the entity's tile.
+    PickUpItem,
+    /// Drink a healing potion from the entity's inventory.
+    QuaffPotion(EntityId),
+    /// Zap a charged wand from the entity's inventory at a foe.
+    ZapWand { wand_id: EntityId, target_id: EntityId },
 }
 
+impl AIAction {
+    /// Scheduler cost category, so monsters pay the same time as the player.
+    pub fn kind(&self) -> ActionKind {
+        match self {
+            AIAction::MeleeAttack(_) | AIAction::BossCharge { .. } => ActionKind::Attack,
+            AIAction::RangedAttack(_) => ActionKind::RangedAttack,
+            AIAction::MoveToward(_) | AIAction::MoveAway(_) | AIAction::MoveRandom => ActionKind::Move,
+            AIAction::Wait => ActionKind::Wait,
+            AIAction::BossSummon { .. } | AIAction::BossTeleport | AIAction::BossFrostBolt => ActionKind::Cast,
+            AIAction::PickUpItem => ActionKind::PickUp,
+            AIAction::QuaffPotion(_) => ActionKind::Quaff,
+            AIAction::ZapWand { .. } => ActionKind::Zap,
+        }
+    }
+}
+
+/// Range at which monsters zap wands at the player.
+pub const MONSTER_WAND_RANGE: i32 = 6;
+
 /// Given an entity's state and the world context, decide what action to take.
 pub fn decide_action(
     entity: &Entity,
@@ -49,8 +76,8 @@ pub fn decide_action(
         return AIAction::MoveRandom;
     }
 
-    if !can_see_player {
-        return AIAction::Wait;
+    if let Some(action) = decide_distracted(entity, map, entities) {
+        return action;
     }
 
     let distance = entity_pos.chebyshev_distance(&player_pos);
@@ -60,6 +87,18 @@ pub fn decide_action(
         .map(|h| h.current as f32 / h.max as f32)
         .unwrap_or(1.0);
 
+    // Allies fight with what they have; their pack is not for use on the player
+    let is_ally = matches!(entity.ai, Some(AIBehavior::Ally { .. }));
+    if !is_ally {
+        if let Some(action) = decide_item_use(entity, player, can_see_player, distance, hp_pct, map, entities) {
+            return action;
+        }
+    }
+
+    if !can_see_player {
+        return AIAction::Wait;
+    }
+
     match &entity.ai {
         Some(AIBehavior::Melee) => decide_melee(entity, player_pos, distance, hp_pct, dijkstra, map, entities),
         Some(AIBehavior::Ranged { range, preferred_distance }) => {
@@ -67,7 +106,8 @@ pub fn decide_action(
         }
         Some(AIBehavior::Passive) => AIAction::Wait,
         Some(AIBehavior::Fleeing) => decide_flee(entity, dijkstra, map, entities),
-        Some(AIBehavior::Boss(phase)) => decide_boss(entity, player, *phase, distance, hp_pct, dijkstra, map, entities),
+        // Scripted moves need the boss's stored encounter; see `decide_boss_action`
+        Some(AIBehavior::Boss(_)) => decide_melee(entity, player_pos, distance, hp_pct, dijkstra, map, entities),
         Some(AIBehavior::Ally { follow_distance }) => {
             decide_ally(entity, player_pos, distance, *follow_distance, dijkstra, map, entities)
         }
@@ -75,6 +115,94 @@ pub fn decide_action(
     }
 }
 
+/// Item-using monsters (those with an inventory): drink a healing potion when
+/// below half HP, zap a charged wand at the player in range, and pick up items
+/// they are standing on unless the player is adjacent.
+fn decide_item_use(
+    entity: &Entity,
+    player: &Entity,
+    can_see_player: bool,
+    distance: i32,
+    hp_pct: f32,
+    map: &Map,
+    entities: &[Entity],
+) -> Option<AIAction> {
+    let inventory = entity.inventory.as_ref()?;
+
+    if hp_pct < 0.5 {
+        let potion = inventory.items.iter().find(|i| {
+            i.item.as_ref().is_some_and(|p| {
+                p.item_type == ItemType::Potion && matches!(p.effect, Some(ItemEffect::Heal(_)))
+            })
+        });
+        if let Some(potion) = potion {
+            return Some(AIAction::QuaffPotion(potion.id));
+        }
+    }
+
+    if can_see_player {
+        if (2..=MONSTER_WAND_RANGE).contains(&distance)
+            && has_line_of_sight(map, entity.position, player.position)
+        {
+            let wand = inventory.items.iter().find(|i| {
+                i.item.as_ref().is_some_and(|p| {
+                    p.item_type == ItemType::Wand
+                        && matches!(p.effect, Some(ItemEffect::RangedAttack { .. }))
+                        && p.charges.is_some_and(|c| c > 0)
+                })
+            });
+            if let Some(wand) = wand {
+                return Some(AIAction::ZapWand { wand_id: wand.id, target_id: player.id });
+            }
+        }
+
+        // Don't stop to loot while the player is in reach
+        if distance <= 1 {
+            return None;
+        }
+    }
+
+    let item_underfoot = entities
+        .iter()
+        .any(|e| e.position == entity.position && e.item.as_ref().is_some_and(is_monster_loot));
+    if item_underfoot && !inventory.is_full() {
+        return Some(AIAction::PickUpItem);
+    }
+
+    None
+}
+
+/// Monsters distracted by thrown food head for the nearest visible morsel
+/// and linger over it, ignoring the player until the distraction wears off.
+fn decide_distracted(entity: &Entity, map: &Map, entities: &[Entity]) -> Option<AIAction> {
+    let distracted = entity
+        .status_effects
+        .iter()
+        .any(|s| s.effect_type == StatusType::Distracted);
+    if !distracted {
+        return None;
+    }
+
+    let food = entities
+        .iter()
+        .filter(|e| e.item.as_ref().is_some_and(|p| p.item_type == ItemType::Food && !p.is_corpse()))
+        .filter(|e| has_line_of_sight(map, entity.position, e.position))
+        .min_by_key(|e| entity.position.chebyshev_distance(&e.position))?;
+
+    let distance = entity.position.chebyshev_distance(&food.position);
+    if distance <= 1 {
+        return Some(AIAction::Wait);
+    }
+
+    let step = Direction::ALL
+        .iter()
+        .map(|dir| entity.position.apply_direction(*dir))
+        .filter(|p| map.is_walkable(p.x, p.y) && !is_blocked_by_entity(*p, entities, entity.id))
+        .min_by_key(|p| p.chebyshev_distance(&food.position))
+        .filter(|p| p.chebyshev_distance(&food.position) < distance);
+    Some(step.map_or(AIAction::Wait, AIAction::MoveToward))
+}
+
 fn decide_melee(
     entity: &Entity,
     _player_pos: Position,
@@ -192,133 +320,103 @@ fn decide_ally(
     AIAction::Wait
 }
 
-fn decide_boss(
+/// Decide a boss's action from its encounter script. Returns the action and
+/// the rotation slot used, if any, so the caller can start its cooldown.
+pub fn decide_boss_action(
     entity: &Entity,
-    _player: &Entity,
-    phase: BossPhase,
-    distance: i32,
-    _hp_pct: f32,
-    dijkstra: &Option<DijkstraMap>,
-    map: &Map,
-    entities: &[Entity],
-) -> AIAction {
-    let is_phase2 = phase == BossPhase::Phase2;
-    let name = entity.name.as_str();
-
-    match name {
-        "Goblin King" => decide_boss_goblin_king(entity, is_phase2, distance, dijkstra, map, entities),
-        "Troll Warlord" => decide_boss_troll_warlord(entity, _player, is_phase2, distance, dijkstra, map, entities),
-        "The Lich" => decide_boss_lich(entity, _player, is_phase2, distance, dijkstra, map, entities),
-        _ => {
-            // Generic boss fallback
-            if distance <= 1 {
-                return AIAction::MeleeAttack(0);
-            }
-            if let Some(next_pos) = toward_position(entity.position, dijkstra, map, entities, entity.id) {
-                AIAction::MoveToward(next_pos)
-            } else {
-                AIAction::Wait
-            }
-        }
-    }
-}
-
-/// Goblin King (floor 3):
-/// - Every 4 turns summon 1-2 Goblins
-/// - Phase 2 (below 50% HP): summon Archers every 3 turns
-/// The action counter check happens in state.rs; here we just return the summon action
-/// when appropriate, falling back to melee/move behavior.
-/// State.rs increments the counter and checks the interval.
-fn decide_boss_goblin_king(
-    entity: &Entity,
-    _is_phase2: bool,
-    distance: i32,
+    player: &Entity,
+    encounter: &BossEncounter,
     dijkstra: &Option<DijkstraMap>,
     map: &Map,
     entities: &[Entity],
-) -> AIAction {
-    // The summon decision is driven by the counter in state.rs.
-    // From the AI perspective, we return the standard melee behavior.
-    // State.rs will check the counter and override with BossSummon when it's time.
-    // We signal readiness to summon by returning BossSummon; state.rs decides interval.
-    // Actually, we can't access the counter here. Return a marker that state.rs interprets.
-    // Strategy: always return normal melee behavior. state.rs wraps this with summon logic.
-    if distance <= 1 {
-        return AIAction::MeleeAttack(0);
-    }
+) -> (AIAction, Option<usize>) {
+    let can_see_player = entity
+        .fov
+        .as_ref()
+        .map(|f| f.visible_tiles.contains(&player.position))
+        .unwrap_or(false);
 
-    if let Some(next_pos) = toward_position(entity.position, dijkstra, map, entities, entity.id) {
-        AIAction::MoveToward(next_pos)
-    } else {
-        AIAction::Wait
-    }
-}
+    let is_confused = entity
+        .status_effects
+        .iter()
+        .any(|s| s.effect_type == StatusType::Confused);
 
-/// Troll Warlord (floor 6):
-/// - At range 2-4: charge (move to adjacent + attack 2x damage)
-/// - Phase 2: charge also stuns 1 turn
-fn decide_boss_troll_warlord(
-    entity: &Entity,
-    _player: &Entity,
-    is_phase2: bool,
-    distance: i32,
-    dijkstra: &Option<DijkstraMap>,
-    map: &Map,
-    entities: &[Entity],
-) -> AIAction {
-    if distance <= 1 {
-        return AIAction::MeleeAttack(0);
+    if is_confused {
+        return (AIAction::MoveRandom, None);
     }
 
-    // Charge range: 2-4 tiles away
-    if distance >= 2 && distance <= 4 {
-        return AIAction::BossCharge { stun: is_phase2 };
+    if !can_see_player {
+        return (AIAction::Wait, None);
     }
 
-    // Otherwise close distance
-    if let Some(next_pos) = toward_position(entity.position, dijkstra, map, entities, entity.id) {
-        AIAction::MoveToward(next_pos)
-    } else {
-        AIAction::Wait
-    }
+    let distance = entity.position.chebyshev_distance(&player.position);
+    decide_boss_rotation(entity, player, encounter, distance, dijkstra, map, entities)
 }
 
-/// The Lich (floor 10):
-/// - If player adjacent: teleport 4-6 tiles away + leave Fire tile at old position
-/// - Phase 2: also cast ranged frost bolt
-fn decide_boss_lich(
+/// Walk the current phase's rotation in priority order and take the first
+/// ready ability whose range condition is met. Falls back to melee/approach.
+fn decide_boss_rotation(
     entity: &Entity,
     player: &Entity,
-    is_phase2: bool,
+    encounter: &BossEncounter,
     distance: i32,
     dijkstra: &Option<DijkstraMap>,
     map: &Map,
     entities: &[Entity],
-) -> AIAction {
-    // If player is adjacent, teleport away
-    if distance <= 1 {
-        return AIAction::BossTeleport;
-    }
+) -> (AIAction, Option<usize>) {
+    for (slot, boss_move) in encounter.current_phase().rotation.iter().enumerate() {
+        if !encounter.is_ready(slot) {
+            continue;
+        }
 
-    // Phase 2: try frost bolt at range if we have LOS
-    if is_phase2 && distance <= 6 && distance >= 2 {
-        if has_line_of_sight(map, entity.position, player.position) {
-            return AIAction::BossFrostBolt;
+        let action = match &boss_move.ability {
+            BossAbility::Summon { minion, max_count } => Some(AIAction::BossSummon {
+                minion: minion.clone(),
+                max_count: *max_count,
+            }),
+            BossAbility::Charge { min_range, max_range, stun } => {
+                if distance >= *min_range && distance <= *max_range {
+                    Some(AIAction::BossCharge { stun: *stun })
+                } else {
+                    None
+                }
+            }
+            BossAbility::Teleport => {
+                if distance <= 1 {
+                    Some(AIAction::BossTeleport)
+                } else {
+                    None
+                }
+            }
+            BossAbility::FrostBolt { range } => {
+                if distance >= 2 && distance <= *range && has_line_of_sight(map, entity.position, player.position) {
+                    Some(AIAction::BossFrostBolt)
+                } else {
+                    None
+                }
+            }
+            BossAbility::RangedAttack { range } => {
+                if distance >= 2 && distance <= *range && has_line_of_sight(map, entity.position, player.position) {
+                    Some(AIAction::RangedAttack(0))
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some(action) = action {
+            return (action, Some(slot));
         }
     }
 
-    // Ranged attack if in range (Lich has innate ranged)
-    if distance <= 6 && distance >= 2 {
-        if has_line_of_sight(map, entity.position, player.position) {
-            return AIAction::RangedAttack(0);
-        }
+    if distance <= 1 {
+        return (AIAction::MeleeAttack(0), None);
     }
 
-    // Close distance
     if let Some(next_pos) = toward_position(entity.position, dijkstra, map, entities, entity.id) {
-        AIAction::MoveToward(next_pos)
+        (AIAction::MoveToward(next_pos), None)
     } else {
-        AIAction::Wait
+        (AIAction::Wait, None)
     }
 }
 
@@ -329,21 +427,21 @@ pub fn activate_passive(entity: &mut Entity) {
     }
 }
 
-/// Check and update boss phase based on HP.
-pub fn check_boss_phase(entity: &mut Entity) -> bool {
+/// Check and update boss phase based on HP. Advances the encounter script and
+/// flags the AI as Phase2 once past the opening phase. Returns the new phase
+/// index on a transition.
+pub fn check_boss_phase(entity: &mut Entity, encounter: &mut BossEncounter) -> Option<usize> {
     let hp_pct = entity
         .health
         .as_ref()
         .map(|h| h.current as f32 / h.max as f32)
         .unwrap_or(1.0);
 
+    let new_phase = encounter.update_phase(hp_pct)?;
     if let Some(AIBehavior::Boss(ref mut phase)) = entity.ai {
-        if hp_pct < 0.5 && *phase == BossPhase::Phase1 {
-            *phase = BossPhase::Phase2;
-            return true;
-        }
+        *phase = BossPhase::Phase2;
     }
-    false
+    Some(new_phase)
 }
 
 // --- Movement helpers ---
@@ -380,6 +478,11 @@ fn flee_position(
     }
 }
 
+/// Monsters carry off anything lying around except keys and corpses.
+pub fn is_monster_loot(item: &ItemProperties) -> bool {
+    item.item_type != ItemType::Key && !item.is_corpse()
+}
+
 fn is_blocked_by_entity(pos: Position, entities: &[Entity], self_id: EntityId) -> bool {
     entities
         .iter()
@@ -432,6 +535,7 @@ mod tests {
                 dodge_chance: 0.0,
                 ranged: None,
                 on_hit: None,
+                resistances: Vec::new(),
             }),
             ai: Some(ai),
             inventory: None,
@@ -473,6 +577,7 @@ mod tests {
                 dodge_chance: 0.0,
                 ranged: None,
                 on_hit: None,
+                resistances: Vec::new(),
             }),
             ai: None,
             inventory: None,
@@ -590,8 +695,10 @@ mod tests {
             30, 100, // 30% HP
         );
 
-        let transitioned = check_boss_phase(&mut boss);
-        assert!(transitioned);
+        let mut encounter = BossEncounter::for_entity(&boss);
+
+        let transitioned = check_boss_phase(&mut boss, &mut encounter);
+        assert_eq!(transitioned, Some(1));
         assert!(matches!(boss.ai, Some(AIBehavior::Boss(BossPhase::Phase2))));
     }
 
@@ -602,6 +709,20 @@ mod tests {
         assert!(matches!(entity.ai, Some(AIBehavior::Melee)));
     }
 
+    /// A boss's scripted action, as the world decides it from a fresh encounter.
+    fn boss_action(
+        boss: &Entity,
+        player: &Entity,
+        dijkstra: &Option<DijkstraMap>,
+        map: &Map,
+        entities: &[Entity],
+    ) -> AIAction {
+        let mut encounter = BossEncounter::for_entity(boss);
+        let hp = boss.health.as_ref().unwrap();
+        encounter.update_phase(hp.current as f32 / hp.max as f32);
+        decide_boss_action(boss, player, &encounter, dijkstra, map, entities).0
+    }
+
     fn make_named_boss(name: &str, id: EntityId, pos: Position, phase: BossPhase, hp: i32, max_hp: i32) -> Entity {
         let mut entity = make_entity_with_ai(id, pos, AIBehavior::Boss(phase), hp, max_hp);
         entity.name = name.to_string();
@@ -618,7 +739,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::MeleeAttack(_)));
     }
 
@@ -630,7 +751,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::MoveToward(_)));
     }
 
@@ -645,7 +766,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::BossCharge { stun: false }));
     }
 
@@ -657,7 +778,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::BossCharge { stun: true }));
     }
 
@@ -669,7 +790,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::MeleeAttack(_)));
     }
 
@@ -682,7 +803,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::MoveToward(_)));
     }
 
@@ -696,7 +817,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::BossTeleport));
     }
 
@@ -709,7 +830,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::RangedAttack(_)));
     }
 
@@ -722,7 +843,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::BossFrostBolt));
     }
 
@@ -735,10 +856,163 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::MoveToward(_)));
+    }
+
+    // --- Item-using monster tests ---
+
+    fn make_item(id: EntityId, pos: Position, item_type: ItemType, effect: Option<ItemEffect>, charges: Option<u32>) -> Entity {
+        let mut item = make_player_entity(pos);
+        item.id = id;
+        item.name = format!("{:?}", item_type);
+        item.blocks_movement = false;
+        item.health = None;
+        item.combat = None;
+        item.item = Some(ItemProperties {
+            item_type,
+            slot: None,
+            power: 0,
+            speed_mod: 0,
+            effect,
+            charges,
+            energy_cost: 100,
+            ammo_type: None,
+            ranged: None,
+            hunger_restore: 0,
+            enchant_level: 0,
+            identified: true,
+            resistances: Vec::new(),
+            cursed: false,
+            curse_known: false,
+            affixes: Vec::new(),
+            quantity: 1,
+            durability: 0,
+            max_durability: 0,
+        });
+        item
+    }
+
+    fn make_item_user(pos: Position, hp: i32, max_hp: i32) -> Entity {
+        let mut entity = make_entity_with_ai(1, pos, AIBehavior::Melee, hp, max_hp);
+        entity.inventory = Some(Inventory::new(4));
+        entity.equipment = Some(EquipmentSlots::empty());
+        entity
+    }
+
+    #[test]
+    fn item_user_quaffs_potion_when_low() {
+        let map = make_open_map();
+        let player = make_player_entity(Position::new(10, 10));
+        let mut goblin = make_item_user(Position::new(11, 10), 5, 20);
+        let potion = make_item(50, goblin.position, ItemType::Potion, Some(ItemEffect::Heal(15)), None);
+        goblin.inventory.as_mut().unwrap().items.push(potion);
+        let entities = vec![player.clone(), goblin.clone()];
+        let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
+
+        let action = decide_action(&goblin, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::QuaffPotion(50)));
+    }
+
+    #[test]
+    fn item_user_zaps_wand_at_range() {
+        let map = make_open_map();
+        let player = make_player_entity(Position::new(10, 10));
+        let mut goblin = make_item_user(Position::new(14, 10), 20, 20);
+        let wand = make_item(51, goblin.position, ItemType::Wand, Some(ItemEffect::RangedAttack { damage: 8, status: None, damage_type: DamageType::Lightning }), Some(3));
+        goblin.inventory.as_mut().unwrap().items.push(wand);
+        let entities = vec![player.clone(), goblin.clone()];
+        let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
+
+        let action = decide_action(&goblin, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::ZapWand { wand_id: 51, target_id: 0 }));
+
+        // The same monster raised as an ally leaves its wand alone
+        goblin.ai = Some(AIBehavior::Ally { follow_distance: 3 });
+        let action = decide_action(&goblin, &player, &dijkstra, &map, &entities);
+        assert!(!matches!(action, AIAction::ZapWand { .. }));
+    }
+
+    #[test]
+    fn item_user_ignores_empty_wand() {
+        let map = make_open_map();
+        let player = make_player_entity(Position::new(10, 10));
+        let mut goblin = make_item_user(Position::new(14, 10), 20, 20);
+        let wand = make_item(51, goblin.position, ItemType::Wand, Some(ItemEffect::RangedAttack { damage: 8, status: None, damage_type: DamageType::Lightning }), Some(0));
+        goblin.inventory.as_mut().unwrap().items.push(wand);
+        let entities = vec![player.clone(), goblin.clone()];
+        let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
+
+        let action = decide_action(&goblin, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::MoveToward(_)));
+    }
+
+    #[test]
+    fn item_user_picks_up_item_underfoot() {
+        let map = make_open_map();
+        let player = make_player_entity(Position::new(10, 10));
+        let goblin = make_item_user(Position::new(14, 10), 20, 20);
+        let potion = make_item(52, goblin.position, ItemType::Potion, Some(ItemEffect::Heal(15)), None);
+        let entities = vec![player.clone(), goblin.clone(), potion];
+        let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
+
+        let action = decide_action(&goblin, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::PickUpItem));
+    }
+
+    #[test]
+    fn item_user_leaves_corpses_alone() {
+        let map = make_open_map();
+        let player = make_player_entity(Position::new(10, 10));
+        let goblin = make_item_user(Position::new(14, 10), 20, 20);
+        let corpse_effect = ItemEffect::Corpse { monster: "Rat".to_string(), cooked: false };
+        let corpse = make_item(52, goblin.position, ItemType::Food, Some(corpse_effect), None);
+        let entities = vec![player.clone(), goblin.clone(), corpse];
+        let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
+
+        let action = decide_action(&goblin, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::MoveToward(_)));
+    }
+
+    #[test]
+    fn monster_without_inventory_ignores_items() {
+        let map = make_open_map();
+        let player = make_player_entity(Position::new(10, 10));
+        let rat = make_entity_with_ai(1, Position::new(14, 10), AIBehavior::Melee, 20, 20);
+        let potion = make_item(52, rat.position, ItemType::Potion, Some(ItemEffect::Heal(15)), None);
+        let entities = vec![player.clone(), rat.clone(), potion];
+        let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
+
+        let action = decide_action(&rat, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::MoveToward(_)));
     }
 
+    #[test]
+    fn distracted_monster_goes_for_food() {
+        let map = make_open_map();
+        let player = make_player_entity(Position::new(10, 10));
+        let mut rat = make_entity_with_ai(1, Position::new(11, 10), AIBehavior::Melee, 20, 20);
+        rat.status_effects.push(StatusEffect {
+            effect_type: StatusType::Distracted,
+            duration: 5,
+            magnitude: 0,
+            source: "food".to_string(),
+        });
+        let food = make_item(53, Position::new(15, 10), ItemType::Food, None, None);
+        let entities = vec![player.clone(), rat.clone(), food];
+        let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
+
+        let action = decide_action(&rat, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::MoveToward(pos) if pos == Position::new(12, 10)));
+
+        // A corpse is no thrown treat; with nothing else to chase, the rat bites.
+        let corpse_effect = ItemEffect::Corpse { monster: "Rat".to_string(), cooked: false };
+        let corpse = make_item(54, Position::new(15, 10), ItemType::Food, Some(corpse_effect), None);
+        let entities = vec![player.clone(), rat.clone(), corpse];
+        let action = decide_action(&rat, &player, &dijkstra, &map, &entities);
+        assert!(matches!(action, AIAction::MeleeAttack(_)));
+    }
+
     // --- Generic boss fallback test ---
 
     #[test]
@@ -749,7 +1023,7 @@ mod tests {
         let entities = vec![player.clone(), boss.clone()];
         let dijkstra = Some(DijkstraMap::compute(&map, &[player.position]));
 
-        let action = decide_action(&boss, &player, &dijkstra, &map, &entities);
+        let action = boss_action(&boss, &player, &dijkstra, &map, &entities);
         assert!(matches!(action, AIAction::MeleeAttack(_)));
     }
 }
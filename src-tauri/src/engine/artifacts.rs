@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+
+use super::entity::*;
+use super::items::find_template;
+
+pub const BLOODTHIRST_BLADE: &str = "Bloodthirst Blade";
+pub const SHADOW_SHROUD: &str = "Shadow Shroud";
+pub const AEGIS_OF_THORNS: &str = "Aegis of Thorns";
+
+/// Each artifact and the first floor it can turn up on.
+const ARTIFACT_FLOORS: [(&str, u32); 3] = [
+    (BLOODTHIRST_BLADE, 3),
+    (AEGIS_OF_THORNS, 4),
+    (SHADOW_SHROUD, 5),
+];
+
+/// Chance a new floor holds one of the artifacts not yet seen this run.
+const ARTIFACT_FLOOR_CHANCE: f32 = 0.2;
+/// Most power the Bloodthirst Blade can gain from kills.
+const BLOODTHIRST_MAX_BONUS: i32 = 8;
+/// The Shadow Shroud cloaks its wearer when a blow drops them below this
+/// fraction of max HP.
+const SHROUD_HP_FRACTION: f32 = 0.3;
+pub const SHROUD_INVISIBLE_TURNS: u32 = 3;
+/// Damage the Aegis of Thorns returns to melee attackers.
+pub const THORNS_DAMAGE: i32 = 2;
+
+/// What an artifact does, for item descriptions. Accepts enchanted names.
+pub fn describe(name: &str) -> Option<&'static str> {
+    match name.split(" +").next().unwrap_or(name) {
+        BLOODTHIRST_BLADE => Some("Gains +1 power for every kill it makes this run (up to +8)."),
+        SHADOW_SHROUD => Some("Wraps its wearer in shadow when a blow leaves them below 30% HP."),
+        AEGIS_OF_THORNS => Some("Melee attackers take 2 damage whenever they strike its bearer."),
+        _ => None,
+    }
+}
+
+/// Pick an artifact to place on a new floor, skipping any already seen.
+pub fn roll_floor_artifact(floor: u32, seen: &HashSet<String>, rng: &mut impl Rng) -> Option<&'static str> {
+    let eligible: Vec<&'static str> = ARTIFACT_FLOORS
+        .iter()
+        .filter(|(name, min_floor)| floor >= *min_floor && !seen.contains(*name))
+        .map(|(name, _)| *name)
+        .collect();
+    if eligible.is_empty() || rng.gen::<f32>() >= ARTIFACT_FLOOR_CHANCE {
+        return None;
+    }
+    Some(eligible[rng.gen_range(0..eligible.len())])
+}
+
+/// The entity's equipped, unbroken copy of the named artifact. Enchanting
+/// appends " +N" to the name, so match on the prefix.
+fn equipped_artifact<'a>(entity: &'a Entity, name: &str) -> Option<&'a Entity> {
+    let (equipment, inventory) = (entity.equipment.as_ref()?, entity.inventory.as_ref()?);
+    [equipment.main_hand, equipment.off_hand, equipment.head, equipment.body, equipment.ring, equipment.amulet]
+        .iter()
+        .flatten()
+        .filter_map(|id| inventory.items.iter().find(|i| i.id == *id))
+        .find(|i| i.name.starts_with(name) && i.item.as_ref().is_some_and(|p| !p.is_broken()))
+}
+
+pub fn has_equipped(entity: &Entity, name: &str) -> bool {
+    equipped_artifact(entity, name).is_some()
+}
+
+/// Feed a kill to a wielded Bloodthirst Blade. Returns the blade's new
+/// power if it grew.
+pub fn feed_bloodthirst(entity: &mut Entity) -> Option<i32> {
+    let blade_id = equipped_artifact(entity, BLOODTHIRST_BLADE)?.id;
+    let base = find_template(BLOODTHIRST_BLADE).map_or(0, |t| t.power);
+    let props = entity
+        .inventory
+        .as_mut()?
+        .items
+        .iter_mut()
+        .find(|i| i.id == blade_id)?
+        .item
+        .as_mut()?;
+    if props.power - props.enchant_level >= base + BLOODTHIRST_MAX_BONUS {
+        return None;
+    }
+    props.power += 1;
+    Some(props.power)
+}
+
+/// Whether a blow that took the wearer from `hp_before` to their current
+/// HP should trigger the Shadow Shroud.
+pub fn shroud_triggers(entity: &Entity, hp_before: i32) -> bool {
+    let health = match &entity.health {
+        Some(h) if h.current > 0 => h,
+        _ => return false,
+    };
+    let threshold = (health.max as f32 * SHROUD_HP_FRACTION) as i32;
+    hp_before >= threshold && health.current < threshold && has_equipped(entity, SHADOW_SHROUD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn artifacts_roll_at_most_once() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut seen = HashSet::new();
+        for _ in 0..200 {
+            if let Some(name) = roll_floor_artifact(10, &seen, &mut rng) {
+                assert!(seen.insert(name.to_string()), "{} rolled twice", name);
+            }
+        }
+        assert_eq!(seen.len(), ARTIFACT_FLOORS.len());
+        assert!((0..200).all(|_| roll_floor_artifact(1, &HashSet::new(), &mut rng).is_none()));
+    }
+
+    #[test]
+    fn artifacts_are_real_items_kept_out_of_loot() {
+        for (name, _) in ARTIFACT_FLOORS {
+            let template = find_template(name).expect("artifact template");
+            assert_eq!(template.min_floor, 99, "{} must not roll as random loot", name);
+            assert!(describe(name).is_some());
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+
+use super::entity::*;
+
+/// Points the player spreads across stats at character creation.
+pub const POINT_BUDGET: u32 = 4;
+pub const MAX_NAME_LEN: usize = 20;
+
+/// What one allocated point buys in each stat.
+pub const HP_PER_POINT: i32 = 5;
+pub const ATTACK_PER_POINT: i32 = 1;
+pub const DEFENSE_PER_POINT: i32 = 1;
+pub const SPEED_PER_POINT: i32 = 5;
+
+pub struct BackgroundDef {
+    pub background: Background,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub max_hp: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: i32,
+    pub max_mana: i32,
+    pub gold: u32,
+    pub items: &'static [&'static str],
+    /// Shared talent granted at rank 1.
+    pub talent: &'static str,
+}
+
+pub const ALL_BACKGROUNDS: [Background; 3] = [Background::Soldier, Background::Scholar, Background::Thief];
+
+pub fn get_def(background: Background) -> BackgroundDef {
+    match background {
+        Background::Soldier => BackgroundDef {
+            background,
+            name: "Soldier",
+            description: "Drilled in the shield wall. +10 max HP, +1 defense and a healing draught.",
+            max_hp: 10,
+            attack: 0,
+            defense: 1,
+            speed: 0,
+            max_mana: 0,
+            gold: 0,
+            items: &["Health Potion"],
+            talent: "guard",
+        },
+        Background::Scholar => BackgroundDef {
+            background,
+            name: "Scholar",
+            description: "Years among dusty tomes. +10 max mana and a pair of scrolls.",
+            max_hp: 0,
+            attack: 0,
+            defense: 0,
+            speed: 0,
+            max_mana: 10,
+            gold: 0,
+            items: &["Scroll of Identify", "Scroll of Reveal"],
+            talent: "empower",
+        },
+        Background::Thief => BackgroundDef {
+            background,
+            name: "Thief",
+            description: "Light fingers and lighter feet. +5 speed, 40 gold and a throwing knife.",
+            max_hp: 0,
+            attack: 0,
+            defense: 0,
+            speed: 5,
+            max_mana: 0,
+            gold: 40,
+            items: &["Throwing Knife"],
+            talent: "swiftness",
+        },
+    }
+}
+
+pub fn parse_background(name: &str) -> Result<Background, String> {
+    ALL_BACKGROUNDS
+        .into_iter()
+        .find(|b| format!("{:?}", b) == name)
+        .ok_or_else(|| format!("Unknown background: {}", name))
+}
+
+/// Free points spent on starting stats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatAllocation {
+    pub hp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub speed: u32,
+}
+
+impl StatAllocation {
+    /// Points spent, or None when the fields are too large to add up.
+    pub fn total(&self) -> Option<u32> {
+        [self.attack, self.defense, self.speed]
+            .into_iter()
+            .try_fold(self.hp, |sum, points| sum.checked_add(points))
+    }
+}
+
+/// Trim a character name and check it can be shown in the log.
+pub fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name cannot be empty.".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("Name must be at most {} characters.", MAX_NAME_LEN));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '\'' | '-')) {
+        return Err("Name may only use letters, numbers, spaces, apostrophes and hyphens.".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// The character creation screen's picks, as sent to `new_game`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NewCharacter {
+    pub name: Option<String>,
+    pub background: Option<String>,
+    pub allocation: StatAllocation,
+}
+
+/// Everything picked on the character creation screen, already validated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharacterOptions {
+    pub name: Option<String>,
+    pub background: Option<Background>,
+    pub allocation: StatAllocation,
+}
+
+impl CharacterOptions {
+    pub fn parse(
+        name: Option<&str>,
+        background: Option<&str>,
+        allocation: Option<StatAllocation>,
+    ) -> Result<Self, String> {
+        let name = match name {
+            Some(n) if !n.trim().is_empty() => Some(validate_name(n)?),
+            _ => None,
+        };
+        let background = match background {
+            Some(b) if !b.is_empty() => Some(parse_background(b)?),
+            _ => None,
+        };
+        let allocation = allocation.unwrap_or_default();
+        match allocation.total() {
+            Some(total) if total <= POINT_BUDGET => {}
+            Some(total) => {
+                return Err(format!("Allocated {} points but only {} are available.", total, POINT_BUDGET));
+            }
+            None => return Err(format!("Allocated more than the {} points available.", POINT_BUDGET)),
+        }
+        Ok(CharacterOptions { name, background, allocation })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundView {
+    pub background: Background,
+    pub name: String,
+    pub description: String,
+    pub items: Vec<String>,
+    pub talent: String,
+}
+
+/// What the character creation screen may offer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreationOptions {
+    pub backgrounds: Vec<BackgroundView>,
+    pub point_budget: u32,
+    pub hp_per_point: i32,
+    pub attack_per_point: i32,
+    pub defense_per_point: i32,
+    pub speed_per_point: i32,
+    pub max_name_len: usize,
+}
+
+pub fn creation_options() -> CreationOptions {
+    let backgrounds = ALL_BACKGROUNDS
+        .into_iter()
+        .map(|b| {
+            let def = get_def(b);
+            let talent = super::talents::find_talent(PlayerClass::Warrior, def.talent)
+                .map(|t| t.name)
+                .unwrap_or(def.talent);
+            BackgroundView {
+                background: b,
+                name: def.name.to_string(),
+                description: def.description.to_string(),
+                items: def.items.iter().map(|s| s.to_string()).collect(),
+                talent: talent.to_string(),
+            }
+        })
+        .collect();
+    CreationOptions {
+        backgrounds,
+        point_budget: POINT_BUDGET,
+        hp_per_point: HP_PER_POINT,
+        attack_per_point: ATTACK_PER_POINT,
+        defense_per_point: DEFENSE_PER_POINT,
+        speed_per_point: SPEED_PER_POINT,
+        max_name_len: MAX_NAME_LEN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::classes::ALL_CLASSES;
+    use crate::engine::{items, talents};
+
+    #[test]
+    fn backgrounds_grant_real_items_and_shared_talents() {
+        for background in ALL_BACKGROUNDS {
+            let def = get_def(background);
+            assert_eq!(parse_background(def.name), Ok(background));
+            for item in def.items {
+                assert!(items::find_template(item).is_some(), "{} gives unknown {}", def.name, item);
+            }
+            for class in ALL_CLASSES {
+                let talent = talents::find_talent(class, def.talent)
+                    .unwrap_or_else(|| panic!("{:?} cannot learn {}", class, def.talent));
+                assert!(talent.class.is_none());
+            }
+        }
+        assert!(parse_background("Bard").is_err());
+    }
+
+    #[test]
+    fn character_options_are_validated() {
+        let spent = StatAllocation { hp: 2, attack: 1, defense: 1, speed: 0 };
+        let opts = CharacterOptions::parse(Some("  Mira  "), Some("Thief"), Some(spent)).unwrap();
+        assert_eq!(opts.name.as_deref(), Some("Mira"));
+        assert_eq!(opts.background, Some(Background::Thief));
+
+        let greedy = StatAllocation { hp: POINT_BUDGET, speed: 1, ..Default::default() };
+        assert!(CharacterOptions::parse(None, None, Some(greedy)).is_err());
+        let wrapping = StatAllocation { hp: u32::MAX, attack: 5, ..Default::default() };
+        assert_eq!(wrapping.total(), None);
+        assert!(CharacterOptions::parse(None, None, Some(wrapping)).is_err());
+        assert!(CharacterOptions::parse(Some("<script>"), None, None).is_err());
+        assert!(CharacterOptions::parse(Some(&"a".repeat(MAX_NAME_LEN + 1)), None, None).is_err());
+        assert_eq!(CharacterOptions::parse(Some("   "), Some(""), None), Ok(CharacterOptions::default()));
+    }
+}
@@ -0,0 +1,397 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::enemies::{
+    apply_endless_scaling, boss_templates, enemy_resistances, get_boss_for_floor, get_enemy_pool, EnemyTemplate,
+};
+use crate::engine::entity::*;
+
+/// A boss ability. Parameters live in data so encounters can be assembled
+/// without touching the AI or turn resolution code.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BossAbility {
+    /// Summon 1..=max_count copies of an enemy template next to the boss.
+    Summon { minion: String, max_count: u32 },
+    /// Rush to the player from min_range..=max_range tiles and strike for 2x damage.
+    Charge { min_range: i32, max_range: i32, stun: bool },
+    /// Blink 4-6 tiles away when the player is adjacent, leaving dark flame behind.
+    Teleport,
+    /// Frost bolt that damages and slows the player.
+    FrostBolt { range: i32 },
+    /// Regular ranged attack using the boss's combat stats.
+    RangedAttack { range: i32 },
+}
+
+/// One entry in a phase's ability rotation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossMove {
+    pub ability: BossAbility,
+    /// Boss turns before the ability can be used again.
+    pub cooldown: u32,
+    /// Boss turns before the first use after the phase begins.
+    pub warmup: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossPhaseDef {
+    /// The phase begins once HP drops below this fraction of max HP (1.0 for the opening phase).
+    pub hp_threshold: f32,
+    /// Abilities in priority order; the first one that is ready and usable is taken.
+    pub rotation: Vec<BossMove>,
+    /// Enemy templates spawned next to the boss when the phase begins.
+    pub adds: Vec<String>,
+    pub enter_message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossDefinition {
+    pub name: String,
+    pub glyph: u32,
+    pub hp: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: i32,
+    pub crit_chance: f32,
+    #[serde(default)]
+    pub resistances: Vec<Resistance>,
+    /// Ordered by descending hp_threshold.
+    pub phases: Vec<BossPhaseDef>,
+}
+
+impl BossDefinition {
+    fn from_template(template: &EnemyTemplate, phases: Vec<BossPhaseDef>) -> Self {
+        Self {
+            name: template.name.to_string(),
+            glyph: template.glyph,
+            hp: template.hp,
+            attack: template.attack,
+            defense: template.defense,
+            speed: template.speed,
+            crit_chance: template.crit_chance,
+            resistances: enemy_resistances(template.name),
+            phases,
+        }
+    }
+
+    /// Index of the phase a boss at `hp_pct` health should be in.
+    pub fn phase_for_hp(&self, hp_pct: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|p| hp_pct < p.hp_threshold)
+            .unwrap_or(0)
+    }
+}
+
+/// Runtime state of a boss fight: the definition plus current phase and cooldowns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossEncounter {
+    pub definition: BossDefinition,
+    pub phase: usize,
+    /// Remaining cooldown per rotation slot of the current phase.
+    pub cooldowns: Vec<u32>,
+}
+
+impl BossEncounter {
+    pub fn new(definition: BossDefinition) -> Self {
+        let mut encounter = Self {
+            definition,
+            phase: 0,
+            cooldowns: Vec::new(),
+        };
+        encounter.reset_cooldowns();
+        encounter
+    }
+
+    /// Encounter for an existing boss entity: its scripted definition if it has
+    /// one, otherwise a plain melee definition built from its stats that still
+    /// turns frenzied below half HP.
+    pub fn for_entity(entity: &Entity) -> Self {
+        let definition = find_definition(&entity.name).unwrap_or_else(|| {
+            let combat = entity.combat.as_ref();
+            BossDefinition {
+                name: entity.name.clone(),
+                glyph: entity.glyph,
+                hp: entity.health.as_ref().map(|h| h.max).unwrap_or(1),
+                attack: combat.map(|c| c.base_attack).unwrap_or(0),
+                defense: combat.map(|c| c.base_defense).unwrap_or(0),
+                speed: combat.map(|c| c.base_speed).unwrap_or(100),
+                crit_chance: combat.map(|c| c.crit_chance).unwrap_or(0.0),
+                resistances: combat.map(|c| c.resistances.clone()).unwrap_or_default(),
+                phases: vec![phase(1.0, Vec::new()), phase(0.5, Vec::new())],
+            }
+        });
+        Self::new(definition)
+    }
+
+    pub fn current_phase(&self) -> &BossPhaseDef {
+        &self.definition.phases[self.phase]
+    }
+
+    fn reset_cooldowns(&mut self) {
+        self.cooldowns = self.current_phase().rotation.iter().map(|m| m.warmup).collect();
+    }
+
+    /// Count down all cooldowns by one boss turn.
+    pub fn tick(&mut self) {
+        for cooldown in &mut self.cooldowns {
+            *cooldown = cooldown.saturating_sub(1);
+        }
+    }
+
+    pub fn is_ready(&self, slot: usize) -> bool {
+        self.cooldowns.get(slot).copied().unwrap_or(0) == 0
+    }
+
+    /// Put the ability in `slot` on cooldown after it has been used.
+    pub fn trigger(&mut self, slot: usize) {
+        if let Some(m) = self.current_phase().rotation.get(slot) {
+            let cooldown = m.cooldown;
+            self.cooldowns[slot] = cooldown;
+        }
+    }
+
+    /// Advance to the phase matching `hp_pct`. Phases never revert when the
+    /// boss heals. Returns the new phase index on a transition.
+    pub fn update_phase(&mut self, hp_pct: f32) -> Option<usize> {
+        let target = self.definition.phase_for_hp(hp_pct);
+        if target > self.phase {
+            self.phase = target;
+            self.reset_cooldowns();
+            Some(target)
+        } else {
+            None
+        }
+    }
+}
+
+fn phase(hp_threshold: f32, rotation: Vec<BossMove>) -> BossPhaseDef {
+    BossPhaseDef {
+        hp_threshold,
+        rotation,
+        adds: Vec::new(),
+        enter_message: None,
+    }
+}
+
+fn ability(ability: BossAbility, cooldown: u32, warmup: u32) -> BossMove {
+    BossMove { ability, cooldown, warmup }
+}
+
+/// Scripted encounters for the fixed boss floors.
+pub fn boss_definitions() -> Vec<BossDefinition> {
+    boss_templates()
+        .iter()
+        .filter_map(|t| {
+            let phases = match t.name {
+                // Summons Goblins every 4 turns, Goblin Archers every 3 below half HP
+                "Goblin King" => vec![
+                    phase(1.0, vec![
+                        ability(BossAbility::Summon { minion: "Goblin".to_string(), max_count: 2 }, 4, 4),
+                    ]),
+                    phase(0.5, vec![
+                        ability(BossAbility::Summon { minion: "Goblin Archer".to_string(), max_count: 2 }, 3, 3),
+                    ]),
+                ],
+                // Charges from 2-4 tiles; the charge also stuns below half HP
+                "Troll Warlord" => vec![
+                    phase(1.0, vec![
+                        ability(BossAbility::Charge { min_range: 2, max_range: 4, stun: false }, 0, 0),
+                    ]),
+                    phase(0.5, vec![
+                        ability(BossAbility::Charge { min_range: 2, max_range: 4, stun: true }, 0, 0),
+                    ]),
+                ],
+                // Teleports away when adjacent; adds frost bolts below half HP
+                "The Lich" => vec![
+                    phase(1.0, vec![
+                        ability(BossAbility::Teleport, 0, 0),
+                        ability(BossAbility::RangedAttack { range: 6 }, 0, 0),
+                    ]),
+                    phase(0.5, vec![
+                        ability(BossAbility::Teleport, 0, 0),
+                        ability(BossAbility::FrostBolt { range: 6 }, 0, 0),
+                        ability(BossAbility::RangedAttack { range: 6 }, 0, 0),
+                    ]),
+                ],
+                _ => return None,
+            };
+            Some(BossDefinition::from_template(t, phases))
+        })
+        .collect()
+}
+
+pub fn find_definition(name: &str) -> Option<BossDefinition> {
+    boss_definitions().into_iter().find(|d| d.name == name)
+}
+
+/// Boss encounter for a floor, if it is a boss floor. Floors 3, 6 and 10 use
+/// the scripted bosses; endless boss floors get a procedurally generated one.
+pub fn boss_for_floor(floor: u32, rng: &mut impl Rng) -> Option<BossDefinition> {
+    if let Some(name) = get_boss_for_floor(floor) {
+        return find_definition(name);
+    }
+    if floor > 10 && floor.is_multiple_of(5) {
+        Some(generate_endless_boss(floor, rng))
+    } else {
+        None
+    }
+}
+
+const ENDLESS_BOSS_NAMES: &[&str] = &[
+    "Vorgath", "Myrrhaxis", "Ulthane", "Grimscale", "Sethrak", "Morwen", "Kharzul", "Ysolde",
+];
+
+const ENDLESS_BOSS_TITLES: &[&str] = &[
+    "the Undying", "the Hollow", "the Ashen", "the Devourer", "the Bonewright", "of the Deep",
+];
+
+/// Abilities an endless boss can draw from, in rotation priority order.
+fn endless_ability_pool(floor: u32, rng: &mut impl Rng) -> Vec<BossMove> {
+    let pool = get_enemy_pool(floor);
+    let minion = pool[rng.gen_range(0..pool.len())].to_string();
+    vec![
+        ability(BossAbility::Summon { minion, max_count: 2 }, 5, 3),
+        ability(BossAbility::Charge { min_range: 2, max_range: 4, stun: floor >= 20 }, 3, 0),
+        ability(BossAbility::Teleport, 4, 0),
+        ability(BossAbility::FrostBolt { range: 6 }, 3, 1),
+        ability(BossAbility::RangedAttack { range: 5 }, 0, 0),
+    ]
+}
+
+/// Build a one-off boss for an endless-mode boss floor. A scripted boss lends
+/// its body (glyph and base stats, scaled with `apply_endless_scaling`), and
+/// each phase adds one ability from the pool plus summoned adds.
+pub fn generate_endless_boss(floor: u32, rng: &mut impl Rng) -> BossDefinition {
+    let templates = boss_templates();
+    let base = &templates[rng.gen_range(0..templates.len())];
+    let (hp, attack, defense) = apply_endless_scaling(base, floor);
+
+    let name = format!(
+        "{} {}",
+        ENDLESS_BOSS_NAMES[rng.gen_range(0..ENDLESS_BOSS_NAMES.len())],
+        ENDLESS_BOSS_TITLES[rng.gen_range(0..ENDLESS_BOSS_TITLES.len())],
+    );
+
+    let pool = endless_ability_pool(floor, rng);
+    let mut remaining: Vec<usize> = (0..pool.len()).collect();
+    let mut chosen: Vec<usize> = Vec::new();
+    let add_pool = get_enemy_pool(floor);
+    let phase_count = if floor >= 25 { 3 } else { 2 };
+
+    let mut phases = Vec::new();
+    for i in 0..phase_count {
+        // Two abilities to start, one more each phase
+        let picks = if i == 0 { 2 } else { 1 };
+        for _ in 0..picks {
+            if !remaining.is_empty() {
+                chosen.push(remaining.remove(rng.gen_range(0..remaining.len())));
+            }
+        }
+        chosen.sort_unstable();
+
+        let (hp_threshold, adds, enter_message) = if i == 0 {
+            (1.0, Vec::new(), None)
+        } else {
+            let adds = (0..i)
+                .map(|_| add_pool[rng.gen_range(0..add_pool.len())].to_string())
+                .collect();
+            let threshold = if i == 1 { 0.5 } else { 0.25 };
+            (threshold, adds, Some(format!("{} howls and calls for aid!", name)))
+        };
+
+        phases.push(BossPhaseDef {
+            hp_threshold,
+            rotation: chosen.iter().map(|&idx| pool[idx].clone()).collect(),
+            adds,
+            enter_message,
+        });
+    }
+
+    BossDefinition {
+        name,
+        glyph: base.glyph,
+        hp,
+        attack,
+        defense,
+        speed: base.speed,
+        crit_chance: base.crit_chance,
+        resistances: enemy_resistances(base.name),
+        phases,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn scripted_bosses_have_definitions() {
+        for t in boss_templates() {
+            let def = find_definition(t.name).expect("boss should have a definition");
+            assert_eq!(def.hp, t.hp);
+            assert!(def.phases.len() >= 2, "{} should have multiple phases", t.name);
+        }
+    }
+
+    #[test]
+    fn phase_for_hp_uses_thresholds() {
+        let def = find_definition("Troll Warlord").unwrap();
+        assert_eq!(def.phase_for_hp(1.0), 0);
+        assert_eq!(def.phase_for_hp(0.6), 0);
+        assert_eq!(def.phase_for_hp(0.4), 1);
+    }
+
+    #[test]
+    fn encounter_phase_never_reverts() {
+        let mut encounter = BossEncounter::new(find_definition("The Lich").unwrap());
+        assert_eq!(encounter.update_phase(0.3), Some(1));
+        assert_eq!(encounter.update_phase(0.9), None);
+        assert_eq!(encounter.phase, 1);
+        assert_eq!(encounter.cooldowns.len(), 3);
+    }
+
+    #[test]
+    fn cooldowns_warm_up_and_reset() {
+        let mut encounter = BossEncounter::new(find_definition("Goblin King").unwrap());
+        assert!(!encounter.is_ready(0));
+        for _ in 0..4 {
+            encounter.tick();
+        }
+        assert!(encounter.is_ready(0));
+        encounter.trigger(0);
+        assert!(!encounter.is_ready(0));
+    }
+
+    #[test]
+    fn boss_floors_get_encounters() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(boss_for_floor(3, &mut rng).unwrap().name, "Goblin King");
+        assert_eq!(boss_for_floor(6, &mut rng).unwrap().name, "Troll Warlord");
+        assert_eq!(boss_for_floor(10, &mut rng).unwrap().name, "The Lich");
+        assert!(boss_for_floor(5, &mut rng).is_none());
+        assert!(boss_for_floor(15, &mut rng).is_some());
+    }
+
+    #[test]
+    fn endless_boss_scales_and_grows_rotation() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let def = generate_endless_boss(25, &mut rng);
+        assert_eq!(def.phases.len(), 3);
+        let base = boss_templates().into_iter().find(|t| t.glyph == def.glyph).unwrap();
+        assert!(def.hp > base.hp);
+        for pair in def.phases.windows(2) {
+            assert!(pair[1].hp_threshold < pair[0].hp_threshold);
+            assert!(pair[1].rotation.len() > pair[0].rotation.len());
+            assert!(!pair[1].adds.is_empty());
+        }
+    }
+
+    #[test]
+    fn endless_boss_is_deterministic() {
+        let a = generate_endless_boss(15, &mut StdRng::seed_from_u64(7));
+        let b = generate_endless_boss(15, &mut StdRng::seed_from_u64(7));
+        assert_eq!(a, b);
+    }
+}
@@ -11,9 +11,14 @@ pub struct ClassTemplate {
     pub fov_radius: i32,
     pub mana: i32,
     pub max_mana: i32,
+    /// Weight carried freely at level 1.
+    pub carry_capacity: u32,
     pub starting_items: Vec<&'static str>,
 }
 
+/// Extra carry capacity gained per level.
+const CARRY_PER_LEVEL: u32 = 3;
+
 pub fn get_class_template(class: PlayerClass) -> ClassTemplate {
     match class {
         PlayerClass::Warrior => ClassTemplate {
@@ -27,6 +32,7 @@ pub fn get_class_template(class: PlayerClass) -> ClassTemplate {
             fov_radius: 8,
             mana: 30,
             max_mana: 30,
+            carry_capacity: 60,
             starting_items: vec!["Short Sword", "Wooden Shield"],
         },
         PlayerClass::Rogue => ClassTemplate {
@@ -40,6 +46,7 @@ pub fn get_class_template(class: PlayerClass) -> ClassTemplate {
             fov_radius: 10,
             mana: 25,
             max_mana: 25,
+            carry_capacity: 45,
             starting_items: vec!["Dagger"],
         },
         PlayerClass::Mage => ClassTemplate {
@@ -53,33 +60,75 @@ pub fn get_class_template(class: PlayerClass) -> ClassTemplate {
             fov_radius: 8,
             mana: 50,
             max_mana: 50,
+            carry_capacity: 40,
+            starting_items: vec!["Staff"],
+        },
+        PlayerClass::Ranger => ClassTemplate {
+            class,
+            hp: 45,
+            attack: 5,
+            defense: 2,
+            speed: 110,
+            crit_chance: 0.10,
+            dodge_chance: 0.05,
+            fov_radius: 10,
+            mana: 30,
+            max_mana: 30,
+            carry_capacity: 50,
+            starting_items: vec!["Shortbow", "Arrow", "Dagger"],
+        },
+        PlayerClass::Cleric => ClassTemplate {
+            class,
+            hp: 50,
+            attack: 5,
+            defense: 3,
+            speed: 95,
+            crit_chance: 0.05,
+            dodge_chance: 0.0,
+            fov_radius: 8,
+            mana: 40,
+            max_mana: 40,
+            carry_capacity: 55,
+            starting_items: vec!["Mace", "Wooden Shield"],
+        },
+        PlayerClass::Necromancer => ClassTemplate {
+            class,
+            hp: 35,
+            attack: 3,
+            defense: 1,
+            speed: 100,
+            crit_chance: 0.05,
+            dodge_chance: 0.0,
+            fov_radius: 8,
+            mana: 45,
+            max_mana: 45,
+            carry_capacity: 40,
             starting_items: vec!["Staff"],
         },
     }
 }
 
-pub fn get_level_up_choices(class: PlayerClass) -> Vec<LevelUpChoice> {
-    let mut choices = vec![
-        LevelUpChoice::MaxHp,
-        LevelUpChoice::Attack,
-        LevelUpChoice::Defense,
-        LevelUpChoice::Speed,
-    ];
-    match class {
-        PlayerClass::Warrior => {
-            choices.push(LevelUpChoice::Cleave);
-            choices.push(LevelUpChoice::Fortify);
-        }
-        PlayerClass::Rogue => {
-            choices.push(LevelUpChoice::Backstab);
-            choices.push(LevelUpChoice::Evasion);
-        }
-        PlayerClass::Mage => {
-            choices.push(LevelUpChoice::SpellPower);
-            choices.push(LevelUpChoice::ManaRegen);
-        }
-    }
-    choices
+/// Every playable class, in menu order.
+pub const ALL_CLASSES: [PlayerClass; 6] = [
+    PlayerClass::Warrior,
+    PlayerClass::Rogue,
+    PlayerClass::Mage,
+    PlayerClass::Ranger,
+    PlayerClass::Cleric,
+    PlayerClass::Necromancer,
+];
+
+/// Parse a class name as sent by the frontend. Unknown names fall back to Warrior.
+pub fn parse_class(name: &str) -> PlayerClass {
+    ALL_CLASSES
+        .into_iter()
+        .find(|c| format!("{:?}", c) == name)
+        .unwrap_or(PlayerClass::Warrior)
+}
+
+/// How much a character of this class and level carries without slowing.
+pub fn carry_capacity(class: PlayerClass, level: u32) -> u32 {
+    get_class_template(class).carry_capacity + level.saturating_sub(1) * CARRY_PER_LEVEL
 }
 
 #[cfg(test)]
@@ -115,18 +164,27 @@ mod tests {
     }
 
     #[test]
-    fn class_level_up_choices() {
-        let warrior = get_level_up_choices(PlayerClass::Warrior);
-        assert!(warrior.contains(&LevelUpChoice::Cleave));
-        assert!(warrior.contains(&LevelUpChoice::Fortify));
-        assert!(!warrior.contains(&LevelUpChoice::Backstab));
+    fn carry_capacity_grows_with_level() {
+        assert_eq!(carry_capacity(PlayerClass::Warrior, 1), 60);
+        assert_eq!(carry_capacity(PlayerClass::Warrior, 3), 66);
+        assert!(carry_capacity(PlayerClass::Mage, 1) < carry_capacity(PlayerClass::Rogue, 1));
+    }
 
-        let rogue = get_level_up_choices(PlayerClass::Rogue);
-        assert!(rogue.contains(&LevelUpChoice::Backstab));
-        assert!(rogue.contains(&LevelUpChoice::Evasion));
+    #[test]
+    fn every_class_starts_with_real_items() {
+        for class in ALL_CLASSES {
+            let t = get_class_template(class);
+            assert!(!t.starting_items.is_empty());
+            for name in &t.starting_items {
+                assert!(crate::engine::items::find_template(name).is_some(), "{:?} starts with unknown {}", class, name);
+            }
+        }
+    }
 
-        let mage = get_level_up_choices(PlayerClass::Mage);
-        assert!(mage.contains(&LevelUpChoice::SpellPower));
-        assert!(mage.contains(&LevelUpChoice::ManaRegen));
+    #[test]
+    fn class_names_parse() {
+        assert_eq!(parse_class("Necromancer"), PlayerClass::Necromancer);
+        assert_eq!(parse_class("Ranger"), PlayerClass::Ranger);
+        assert_eq!(parse_class("Bard"), PlayerClass::Warrior);
     }
 }
@@ -1,6 +1,9 @@
 use rand::Rng;
 
+use super::affixes;
 use super::entity::*;
+use super::inventory;
+use super::sets;
 
 /// Calculate effective attack for an entity, factoring equipment and status effects.
 pub fn effective_attack(entity: &Entity) -> i32 {
@@ -13,18 +16,18 @@ pub fn effective_attack(entity: &Entity) -> i32 {
     let ring_bonus = equipment_ring_bonus(entity);
 
     // Status modifiers
-    let status_mod = status_attack_modifier(entity);
+    let status_mod = status_attack_modifier(entity) - hunger(entity).attack_penalty();
 
-    (base + weapon_bonus + ring_bonus + status_mod).max(0)
+    (base + weapon_bonus + ring_bonus + sets::attack_bonus(entity) + status_mod).max(0)
 }
 
 /// Calculate effective defense for an entity, factoring equipment and status effects.
 pub fn effective_defense(entity: &Entity) -> i32 {
     let base = entity.combat.as_ref().map_or(0, |c| c.base_defense);
 
-    let armor_bonus = equipment_defense_bonus(entity);
+    let armor_bonus = equipment_defense_bonus(entity) + affixes::defense_bonus(entity) + sets::defense_bonus(entity);
 
-    let status_mod = status_defense_modifier(entity);
+    let status_mod = status_defense_modifier(entity) - hunger(entity).defense_penalty();
 
     (base + armor_bonus + status_mod).max(0)
 }
@@ -33,10 +36,22 @@ pub fn effective_defense(entity: &Entity) -> i32 {
 pub fn effective_speed(entity: &Entity) -> i32 {
     let base = entity.combat.as_ref().map_or(100, |c| c.base_speed);
 
-    let equip_mod = equipment_speed_bonus(entity);
-    let status_mod = status_speed_modifier(entity);
+    let equip_mod = equipment_speed_bonus(entity) + affixes::speed_bonus(entity) + sets::speed_bonus(entity);
+    let status_mod = status_speed_modifier(entity) - hunger(entity).speed_penalty();
+    let burden = inventory::encumbrance(entity).speed_penalty();
 
-    (base + equip_mod + status_mod).clamp(10, 200) // 10 minimum prevents softlock, 200 cap prevents abuse
+    (base + equip_mod + status_mod - burden).clamp(10, 200) // 10 minimum prevents softlock, 200 cap prevents abuse
+}
+
+/// Crit chance from base stats plus Keen equipment.
+pub fn effective_crit_chance(entity: &Entity) -> f32 {
+    entity.combat.as_ref().map_or(0.0, |c| c.crit_chance) + affixes::crit_bonus(entity)
+}
+
+/// Dodge chance from base stats, less whatever a heavy load costs.
+pub fn effective_dodge_chance(entity: &Entity) -> f32 {
+    let base = entity.combat.as_ref().map_or(0.0, |c| c.dodge_chance);
+    (base - inventory::encumbrance(entity).dodge_penalty()).clamp(0.0, 1.0)
 }
 
 /// Resolve an attack between attacker and target. Returns (damage, is_crit, killed).
@@ -66,12 +81,15 @@ pub fn resolve_attack(
     let mut damage = (base_damage + variance).max(1); // minimum 1 damage
 
     // Crit check
-    let crit_chance = attacker.combat.as_ref().map_or(0.0, |c| c.crit_chance);
+    let crit_chance = effective_crit_chance(attacker);
     let is_crit = rng.gen::<f32>() < crit_chance;
     if is_crit {
         damage = (damage as f64 * 1.5).round() as i32;
     }
 
+    let damage_type = attack_damage_type(attacker);
+    let damage = resisted_blow(attacker, target, damage);
+
     let target_hp = target.health.as_ref().map_or(0, |h| h.current);
     let killed = damage >= target_hp;
 
@@ -79,6 +97,7 @@ pub fn resolve_attack(
         damage,
         is_crit,
         killed,
+        damage_type,
     }
 }
 
@@ -86,6 +105,7 @@ pub struct AttackResult {
     pub damage: i32,
     pub is_crit: bool,
     pub killed: bool,
+    pub damage_type: DamageType,
 }
 
 /// Resolve a ranged attack. damage_bonus comes from the weapon's ranged stats.
@@ -95,6 +115,36 @@ pub fn resolve_ranged_attack(
     damage_bonus: i32,
     rng: &mut impl Rng,
 ) -> AttackResult {
+    let (damage, is_crit) = roll_ranged_damage(attacker, target, damage_bonus, rng);
+    let damage_type = attack_damage_type(attacker);
+    let damage = resisted_blow(attacker, target, damage);
+
+    let target_hp = target.health.as_ref().map_or(0, |h| h.current);
+    let killed = damage >= target_hp;
+
+    AttackResult { damage, is_crit, killed, damage_type }
+}
+
+/// Resolve a thrown item. It strikes with its own weight rather than the
+/// thrower's weapon, so the hit is physical whatever is in hand.
+pub fn resolve_thrown_attack(
+    attacker: &Entity,
+    target: &Entity,
+    damage_bonus: i32,
+    rng: &mut impl Rng,
+) -> AttackResult {
+    let (damage, is_crit) = roll_ranged_damage(attacker, target, damage_bonus, rng);
+    let damage_type = DamageType::Physical;
+    let damage = apply_resistance(target, damage, damage_type);
+
+    let target_hp = target.health.as_ref().map_or(0, |h| h.current);
+    let killed = damage >= target_hp;
+
+    AttackResult { damage, is_crit, killed, damage_type }
+}
+
+/// Rolled damage of a ranged hit before resistances, and whether it crit.
+fn roll_ranged_damage(attacker: &Entity, target: &Entity, damage_bonus: i32, rng: &mut impl Rng) -> (i32, bool) {
     let atk = effective_attack(attacker) + damage_bonus;
     let def = effective_defense(target);
 
@@ -109,16 +159,183 @@ pub fn resolve_ranged_attack(
 
     let mut damage = (base_damage + variance).max(1);
 
-    let crit_chance = attacker.combat.as_ref().map_or(0.0, |c| c.crit_chance);
+    let crit_chance = effective_crit_chance(attacker);
     let is_crit = rng.gen::<f32>() < crit_chance;
     if is_crit {
         damage = (damage as f64 * 1.5).round() as i32;
     }
+    (damage, is_crit)
+}
+
+/// Share of an elemental weapon's blow dealt as its element; the rest is
+/// physical.
+const ELEMENTAL_SHARE_PCT: i32 = 50;
+
+/// Damage a basic attack deals after the target's resistances. An elemental
+/// weapon's blow is part steel, part element, and each part is resisted on
+/// its own, so a fire-immune foe still feels the blade.
+fn resisted_blow(attacker: &Entity, target: &Entity, damage: i32) -> i32 {
+    match weapon_damage_type(attacker) {
+        Some(element) => {
+            let elemental = damage * ELEMENTAL_SHARE_PCT / 100;
+            apply_resistance(target, damage - elemental, DamageType::Physical)
+                + apply_resistance(target, elemental, element)
+        }
+        None => apply_resistance(target, damage, attack_damage_type(attacker)),
+    }
+}
+
+/// Damage type dealt by an entity's basic attacks. An elemental weapon
+/// (e.g. Flame Blade, Frost Brand) takes precedence over innate on-hit effects.
+pub fn attack_damage_type(entity: &Entity) -> DamageType {
+    if let Some(damage_type) = weapon_damage_type(entity) {
+        return damage_type;
+    }
+    match entity.combat.as_ref().and_then(|c| c.on_hit.as_ref()) {
+        Some(OnHitEffect::Burn { .. }) => DamageType::Fire,
+        Some(OnHitEffect::Poison { .. }) => DamageType::Poison,
+        Some(OnHitEffect::Slow { .. }) => DamageType::Cold,
+        Some(OnHitEffect::LifeSteal) | Some(OnHitEffect::DrainMaxHp) => DamageType::Necrotic,
+        _ => DamageType::Physical,
+    }
+}
+
+/// Total resistance percentage against a damage type, from innate stats and
+/// equipped items. Clamped so nothing takes more than triple damage.
+pub fn resistance(entity: &Entity, damage_type: DamageType) -> i32 {
+    let innate: i32 = entity
+        .combat
+        .as_ref()
+        .map_or(0, |c| sum_resistances(&c.resistances, damage_type));
+    (innate + equipment_resistance(entity, damage_type) + status_resistance(entity, damage_type)).clamp(-200, 100)
+}
 
+/// Resistance from warding effects, such as eating a Fire Elemental's core.
+fn status_resistance(entity: &Entity, damage_type: DamageType) -> i32 {
+    entity
+        .status_effects
+        .iter()
+        .filter(|s| match s.effect_type {
+            StatusType::FireWard => damage_type == DamageType::Fire,
+            StatusType::FrostWard => damage_type == DamageType::Cold,
+            _ => false,
+        })
+        .map(|s| s.magnitude)
+        .sum()
+}
+
+/// Scale incoming damage by the target's resistance. Immune targets take 0;
+/// anything else still takes at least 1.
+pub fn apply_resistance(target: &Entity, damage: i32, damage_type: DamageType) -> i32 {
+    if damage <= 0 {
+        return damage;
+    }
+    let pct = resistance(target, damage_type);
+    if pct >= 100 {
+        return 0;
+    }
+    (damage * (100 - pct) / 100).max(1)
+}
+
+fn weapon_damage_type(entity: &Entity) -> Option<DamageType> {
+    let equipment = entity.equipment.as_ref()?;
+    let inventory = entity.inventory.as_ref()?;
+    let weapon_id = equipment.main_hand?;
+    let weapon = inventory.items.iter().find(|i| i.id == weapon_id)?;
+    let props = weapon.item.as_ref().filter(|p| !p.is_broken())?;
+    match props.effect.as_ref()? {
+        ItemEffect::ApplyStatus { effect: StatusType::Burning, .. } => Some(DamageType::Fire),
+        ItemEffect::ApplyStatus { effect: StatusType::Slowed, .. } => Some(DamageType::Cold),
+        ItemEffect::ApplyStatus { effect: StatusType::Poison, .. } => Some(DamageType::Poison),
+        _ => None,
+    }
+}
+
+fn sum_resistances(resistances: &[Resistance], damage_type: DamageType) -> i32 {
+    resistances
+        .iter()
+        .filter(|r| r.damage_type == damage_type)
+        .map(|r| r.percent)
+        .sum()
+}
+
+// --- Forecasting ---
+
+/// Enumerate every outcome `resolve_attack` can produce without touching an RNG.
+/// `flat_bonus` and `multiplier` mirror the world's post-roll adjustments
+/// (cleave bonus, GlassCannon).
+pub fn forecast_attack(attacker: &Entity, target: &Entity, flat_bonus: i32, multiplier: i32) -> AttackForecast {
+    let atk = effective_attack(attacker);
+    let def = effective_defense(target);
+    let base_damage = (atk - def).max(0);
+    let range = if base_damage > 0 { (base_damage as f64 * 0.2).ceil() as i32 } else { 0 };
+
+    let crit_chance = effective_crit_chance(attacker).clamp(0.0, 1.0);
+    let damage_type = attack_damage_type(attacker);
+    let roll_chance = 1.0 / (2 * range + 1) as f32;
+
+    let mut outcomes = Vec::new();
+    for variance in -range..=range {
+        let rolled = (base_damage + variance).max(1);
+        let crit = (rolled as f64 * 1.5).round() as i32;
+        outcomes.push((
+            adjusted_damage(resisted_blow(attacker, target, rolled), flat_bonus, multiplier),
+            roll_chance * (1.0 - crit_chance),
+        ));
+        outcomes.push((
+            adjusted_damage(resisted_blow(attacker, target, crit), flat_bonus, multiplier),
+            roll_chance * crit_chance,
+        ));
+    }
+
+    let dodge_chance = effective_dodge_chance(target);
+    summarize_outcomes(target, damage_type, &outcomes, 1.0 - dodge_chance, crit_chance)
+}
+
+/// Forecast for fixed-damage abilities, which never vary, crit or get dodged.
+pub fn forecast_fixed_damage(target: &Entity, amount: i32, damage_type: DamageType, multiplier: i32) -> AttackForecast {
+    let damage = adjusted_damage(apply_resistance(target, amount, damage_type), 0, multiplier);
+    summarize_outcomes(target, damage_type, &[(damage, 1.0)], 1.0, 0.0)
+}
+
+/// Resisted damage with the world's flat bonus and multiplier applied.
+fn adjusted_damage(damage: i32, flat_bonus: i32, multiplier: i32) -> i32 {
+    let damage = if damage > 0 { damage + flat_bonus } else { damage };
+    damage * multiplier
+}
+
+fn summarize_outcomes(
+    target: &Entity,
+    damage_type: DamageType,
+    outcomes: &[(i32, f32)],
+    hit_chance: f32,
+    crit_chance: f32,
+) -> AttackForecast {
     let target_hp = target.health.as_ref().map_or(0, |h| h.current);
-    let killed = damage >= target_hp;
+    let possible = outcomes.iter().filter(|(_, p)| *p > 0.0);
+
+    let min_damage = possible.clone().map(|(d, _)| *d).min().unwrap_or(0);
+    let max_damage = possible.map(|(d, _)| *d).max().unwrap_or(0);
+    let avg_damage: f32 = outcomes.iter().map(|(d, p)| *d as f32 * p).sum();
+    let lethal: f32 = outcomes.iter().filter(|(d, _)| *d >= target_hp).map(|(_, p)| p).sum();
+
+    let expected_per_attack = avg_damage * hit_chance;
+    let hits_to_kill = if expected_per_attack > 0.0 {
+        Some((target_hp.max(1) as f32 / expected_per_attack).ceil() as u32)
+    } else {
+        None
+    };
 
-    AttackResult { damage, is_crit, killed }
+    AttackForecast {
+        damage_type: damage_type.name().to_string(),
+        min_damage,
+        max_damage,
+        avg_damage,
+        hit_chance,
+        crit_chance,
+        kill_chance: (lethal * hit_chance).clamp(0.0, 1.0),
+        hits_to_kill,
+    }
 }
 
 /// Get the ranged stats from the equipped weapon, if any.
@@ -159,7 +376,7 @@ fn equipment_attack_bonus(entity: &Entity) -> i32 {
     if let Some(weapon_id) = equipment.main_hand {
         if let Some(item) = inventory.items.iter().find(|i| i.id == weapon_id) {
             if let Some(props) = &item.item {
-                if props.item_type == ItemType::Weapon {
+                if props.item_type == ItemType::Weapon && !props.is_broken() {
                     bonus += props.power;
                 }
             }
@@ -191,7 +408,8 @@ fn equipment_defense_bonus(entity: &Entity) -> i32 {
     for slot_id in slots.iter().flatten() {
         if let Some(item) = inventory.items.iter().find(|i| i.id == *slot_id) {
             if let Some(props) = &item.item {
-                if props.item_type == ItemType::Armor || props.item_type == ItemType::Shield {
+                let is_armor = props.item_type == ItemType::Armor || props.item_type == ItemType::Shield;
+                if is_armor && !props.is_broken() {
                     bonus += props.power;
                 }
             }
@@ -202,7 +420,7 @@ fn equipment_defense_bonus(entity: &Entity) -> i32 {
     if let Some(ring_id) = equipment.ring {
         if let Some(item) = inventory.items.iter().find(|i| i.id == ring_id) {
             if item.name == "Ring of Protection" {
-                if let Some(props) = &item.item {
+                if let Some(props) = item.item.as_ref().filter(|p| !p.is_broken()) {
                     bonus += props.power;
                 }
             }
@@ -236,7 +454,7 @@ fn equipment_speed_bonus(entity: &Entity) -> i32 {
 
     for slot_id in slots.iter().flatten() {
         if let Some(item) = inventory.items.iter().find(|i| i.id == *slot_id) {
-            if let Some(props) = &item.item {
+            if let Some(props) = item.item.as_ref().filter(|p| !p.is_broken()) {
                 bonus += props.speed_mod;
             }
         }
@@ -245,6 +463,36 @@ fn equipment_speed_bonus(entity: &Entity) -> i32 {
     bonus
 }
 
+fn equipment_resistance(entity: &Entity, damage_type: DamageType) -> i32 {
+    let equipment = match &entity.equipment {
+        Some(e) => e,
+        None => return 0,
+    };
+    let inventory = match &entity.inventory {
+        Some(inv) => inv,
+        None => return 0,
+    };
+
+    let slots = [
+        equipment.main_hand,
+        equipment.off_hand,
+        equipment.head,
+        equipment.body,
+        equipment.ring,
+        equipment.amulet,
+    ];
+
+    let mut total = 0;
+    for slot_id in slots.iter().flatten() {
+        if let Some(item) = inventory.items.iter().find(|i| i.id == *slot_id) {
+            if let Some(props) = item.item.as_ref().filter(|p| !p.is_broken()) {
+                total += sum_resistances(&props.resistances, damage_type);
+            }
+        }
+    }
+    total
+}
+
 fn equipment_ring_bonus(entity: &Entity) -> i32 {
     let equipment = match &entity.equipment {
         Some(e) => e,
@@ -258,7 +506,7 @@ fn equipment_ring_bonus(entity: &Entity) -> i32 {
     if let Some(ring_id) = equipment.ring {
         if let Some(item) = inventory.items.iter().find(|i| i.id == ring_id) {
             if item.name == "Ring of Strength" {
-                if let Some(props) = &item.item {
+                if let Some(props) = item.item.as_ref().filter(|p| !p.is_broken()) {
                     return props.power;
                 }
             }
@@ -267,12 +515,21 @@ fn equipment_ring_bonus(entity: &Entity) -> i32 {
     0
 }
 
+fn hunger(entity: &Entity) -> HungerState {
+    entity
+        .status_effects
+        .iter()
+        .find(|s| s.effect_type == StatusType::Hunger)
+        .map_or(HungerState::default(), |s| HungerState::for_level(s.magnitude))
+}
+
 fn status_attack_modifier(entity: &Entity) -> i32 {
     let mut modifier = 0;
     for effect in &entity.status_effects {
         match effect.effect_type {
             StatusType::Weakened => modifier -= 3,
             StatusType::Strengthened => modifier += 3,
+            StatusType::Diseased => modifier -= 2,
             _ => {}
         }
     }
@@ -284,6 +541,7 @@ fn status_defense_modifier(entity: &Entity) -> i32 {
     for effect in &entity.status_effects {
         match effect.effect_type {
             StatusType::Weakened => modifier -= 2,
+            StatusType::Diseased => modifier -= 1,
             _ => {}
         }
     }
@@ -325,6 +583,7 @@ mod tests {
                 dodge_chance: 0.0,
                 ranged: None,
                 on_hit: None,
+                resistances: Vec::new(),
             }),
             ai: None,
             inventory: Some(Inventory::new(20)),
@@ -362,6 +621,7 @@ mod tests {
                 dodge_chance: 0.0,
                 ranged: None,
                 on_hit: None,
+                resistances: Vec::new(),
             }),
             ai: Some(AIBehavior::Melee),
             inventory: None,
@@ -425,6 +685,161 @@ mod tests {
         assert!(result.killed);
     }
 
+    #[test]
+    fn vulnerability_doubles_damage() {
+        let mut golem = make_enemy(60, 0, 0);
+        golem.combat.as_mut().unwrap().resistances = vec![Resistance::new(DamageType::Fire, -100)];
+        assert_eq!(apply_resistance(&golem, 10, DamageType::Fire), 20);
+        assert_eq!(apply_resistance(&golem, 10, DamageType::Physical), 10);
+    }
+
+    #[test]
+    fn immunity_blocks_typed_attacks() {
+        let mut attacker = make_enemy(10, 20, 0);
+        attacker.combat.as_mut().unwrap().on_hit = Some(OnHitEffect::Poison { damage: 2, duration: 3 });
+        let mut skeleton = make_enemy(30, 0, 0);
+        skeleton.combat.as_mut().unwrap().resistances = vec![Resistance::new(DamageType::Poison, 100)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+        let result = resolve_attack(&attacker, &skeleton, &mut rng);
+        assert_eq!(result.damage_type, DamageType::Poison);
+        assert_eq!(result.damage, 0);
+        assert!(!result.killed);
+    }
+
+    #[test]
+    fn thrown_items_hit_physically_through_resistance() {
+        let mut thrower = make_enemy(10, 20, 0);
+        thrower.combat.as_mut().unwrap().on_hit = Some(OnHitEffect::Poison { damage: 2, duration: 3 });
+        let mut wraith = make_enemy(100, 0, 0);
+        wraith.combat.as_mut().unwrap().resistances = vec![Resistance::new(DamageType::Physical, 100)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+        let result = resolve_thrown_attack(&thrower, &wraith, 0, &mut rng);
+        assert_eq!(result.damage_type, DamageType::Physical);
+        assert_eq!(result.damage, 0);
+    }
+
+    #[test]
+    fn equipped_items_add_resistance() {
+        let mut player = make_player();
+        let mut amulet = make_enemy(1, 0, 0);
+        amulet.id = 200;
+        amulet.combat = None;
+        amulet.item = Some(ItemProperties {
+            item_type: ItemType::Amulet,
+            slot: Some(EquipSlot::Amulet),
+            power: 0,
+            speed_mod: 0,
+            effect: None,
+            charges: None,
+            energy_cost: 100,
+            ammo_type: None,
+            ranged: None,
+            hunger_restore: 0,
+            enchant_level: 0,
+            identified: true,
+            resistances: vec![Resistance::new(DamageType::Fire, 50)],
+            cursed: false,
+            curse_known: false,
+            affixes: Vec::new(),
+            quantity: 1,
+            durability: 0,
+            max_durability: 0,
+        });
+        player.inventory.as_mut().unwrap().items.push(amulet);
+        assert_eq!(resistance(&player, DamageType::Fire), 0);
+
+        player.equipment.as_mut().unwrap().amulet = Some(200);
+        assert_eq!(resistance(&player, DamageType::Fire), 50);
+        assert_eq!(apply_resistance(&player, 9, DamageType::Fire), 4);
+        assert_eq!(apply_resistance(&player, 1, DamageType::Fire), 1);
+    }
+
+    #[test]
+    fn elemental_weapon_sets_damage_type() {
+        let mut player = make_player();
+        assert_eq!(attack_damage_type(&player), DamageType::Physical);
+
+        let mut brand = make_enemy(1, 0, 0);
+        brand.id = 201;
+        brand.combat = None;
+        brand.item = Some(ItemProperties {
+            item_type: ItemType::Weapon,
+            slot: Some(EquipSlot::MainHand),
+            power: 8,
+            speed_mod: 0,
+            effect: Some(ItemEffect::ApplyStatus { effect: StatusType::Slowed, duration: 3 }),
+            charges: None,
+            energy_cost: 100,
+            ammo_type: None,
+            ranged: None,
+            hunger_restore: 0,
+            enchant_level: 0,
+            identified: true,
+            resistances: Vec::new(),
+            cursed: false,
+            curse_known: false,
+            affixes: Vec::new(),
+            quantity: 1,
+            durability: 0,
+            max_durability: 0,
+        });
+        player.inventory.as_mut().unwrap().items.push(brand);
+        player.equipment.as_mut().unwrap().main_hand = Some(201);
+        assert_eq!(attack_damage_type(&player), DamageType::Cold);
+
+        // A cold-immune foe shrugs off the frost but not the steel
+        let mut golem = make_enemy(100, 0, 0);
+        let plain = forecast_attack(&player, &golem, 0, 1);
+        golem.combat.as_mut().unwrap().resistances = vec![Resistance::new(DamageType::Cold, 100)];
+        let immune = forecast_attack(&player, &golem, 0, 1);
+        assert!(immune.min_damage > 0);
+        assert!(immune.max_damage < plain.max_damage);
+    }
+
+    #[test]
+    fn forecast_brackets_resolved_attacks() {
+        let mut attacker = make_enemy(30, 12, 0);
+        attacker.combat.as_mut().unwrap().crit_chance = 0.25;
+        let target = make_enemy(40, 0, 2);
+        let forecast = forecast_attack(&attacker, &target, 0, 1);
+        assert_eq!(forecast.hit_chance, 1.0);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut total = 0;
+        for _ in 0..2000 {
+            let result = resolve_attack(&attacker, &target, &mut rng);
+            assert!(result.damage >= forecast.min_damage && result.damage <= forecast.max_damage);
+            total += result.damage;
+        }
+        let observed = total as f32 / 2000.0;
+        assert!((observed - forecast.avg_damage).abs() < 0.5, "{} vs {}", observed, forecast.avg_damage);
+    }
+
+    #[test]
+    fn forecast_kill_chance_and_dodge() {
+        let attacker = make_enemy(30, 20, 0);
+        let mut target = make_enemy(5, 0, 0);
+        let forecast = forecast_attack(&attacker, &target, 0, 1);
+        assert_eq!(forecast.kill_chance, 1.0);
+        assert_eq!(forecast.hits_to_kill, Some(1));
+
+        target.combat.as_mut().unwrap().dodge_chance = 0.25;
+        let forecast = forecast_attack(&attacker, &target, 0, 1);
+        assert!((forecast.kill_chance - 0.75).abs() < 1e-4);
+    }
+
+    #[test]
+    fn forecast_fixed_damage_respects_immunity() {
+        let mut elemental = make_enemy(35, 0, 0);
+        elemental.combat.as_mut().unwrap().resistances = vec![Resistance::new(DamageType::Fire, 100)];
+        let forecast = forecast_fixed_damage(&elemental, 12, DamageType::Fire, 1);
+        assert_eq!(forecast.max_damage, 0);
+        assert_eq!(forecast.kill_chance, 0.0);
+        assert_eq!(forecast.hits_to_kill, None);
+    }
+
     #[test]
     fn speed_with_haste() {
         let mut player = make_player();
@@ -479,6 +894,13 @@ mod tests {
                 hunger_restore: 0,
                 enchant_level: 0,
                 identified: true,
+                resistances: Vec::new(),
+                cursed: false,
+                curse_known: false,
+                affixes: Vec::new(),
+                quantity: 1,
+                durability: 0,
+                max_durability: 0,
             }),
             status_effects: Vec::new(),
             fov: None,
@@ -498,6 +920,69 @@ mod tests {
         assert_eq!(effective_speed(&player), 120); // 100 base + 20 speed_mod
     }
 
+    #[test]
+    fn equipped_affixes_raise_stats() {
+        let mut player = make_player();
+        let base_defense = effective_defense(&player);
+        let base_crit = effective_crit_chance(&player);
+        let sword = Entity {
+            id: 51,
+            name: "Short Sword".to_string(),
+            position: Position::new(0, 0),
+            glyph: 0x2F,
+            render_order: RenderOrder::Item,
+            blocks_movement: false,
+            blocks_fov: false,
+            health: None,
+            combat: None,
+            ai: None,
+            inventory: None,
+            equipment: None,
+            item: Some(ItemProperties {
+                item_type: ItemType::Weapon,
+                slot: Some(EquipSlot::MainHand),
+                power: 0,
+                speed_mod: 0,
+                effect: None,
+                charges: None,
+                energy_cost: 100,
+                ammo_type: None,
+                ranged: None,
+                hunger_restore: 0,
+                enchant_level: 0,
+                identified: true,
+                resistances: Vec::new(),
+                cursed: false,
+                curse_known: false,
+                affixes: vec![
+                    Affix { kind: AffixKind::Keen, tier: 2 },
+                    Affix { kind: AffixKind::Haste, tier: 1 },
+                    Affix { kind: AffixKind::Warding, tier: 3 },
+                ],
+                quantity: 1,
+                durability: 0,
+                max_durability: 0,
+            }),
+            status_effects: Vec::new(),
+            fov: None,
+            door: None,
+            trap: None,
+            stair: None,
+            loot_table: None,
+            flavor_text: None,
+            shop: None,
+            interactive: None,
+            elite: None,
+            resurrection_timer: None,
+        };
+        player.inventory.as_mut().unwrap().items.push(sword);
+        player.equipment.as_mut().unwrap().main_hand = Some(51);
+
+        assert_eq!(effective_speed(&player), 110);
+        assert_eq!(effective_defense(&player), base_defense + 3);
+        assert!((effective_crit_chance(&player) - (base_crit + 0.10)).abs() < 1e-6);
+    }
+
     #[test]
     fn ranged_attack_damage_with_bonus() {
         let attacker = make_player(); // 5 atk
@@ -555,6 +1040,13 @@ mod tests {
                 hunger_restore: 0,
                 enchant_level: 0,
                 identified: true,
+                resistances: Vec::new(),
+                cursed: false,
+                curse_known: false,
+                affixes: Vec::new(),
+                quantity: 1,
+                durability: 0,
+                max_durability: 0,
             }),
             status_effects: Vec::new(),
             fov: None,
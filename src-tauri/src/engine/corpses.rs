@@ -0,0 +1,199 @@
+use super::dungeon::placement::next_id;
+use super::entity::*;
+
+/// Chance a slain edible monster leaves its corpse behind.
+pub const CORPSE_CHANCE: f32 = 0.5;
+/// Extra nutrition, in percent, a corpse gives once cooked.
+pub const COOKED_NUTRITION_BONUS: i32 = 50;
+
+/// A status a corpse inflicts or grants when eaten.
+#[derive(Debug, Clone, Copy)]
+pub struct CorpseEffect {
+    pub status: StatusType,
+    pub duration: u32,
+    pub magnitude: i32,
+}
+
+pub struct CorpseDef {
+    pub monster: &'static str,
+    /// Name of the item left behind.
+    pub item_name: &'static str,
+    pub nutrition: i32,
+    /// Cores are eaten as they are; flesh can go over a fire.
+    pub cookable: bool,
+    /// Chance that eating it raw brings on `sickness`.
+    pub raw_risk: f32,
+    pub sickness: CorpseEffect,
+    /// Granted on eating, raw or cooked.
+    pub boon: Option<CorpseEffect>,
+}
+
+const DISEASE: CorpseEffect = CorpseEffect { status: StatusType::Diseased, duration: 100, magnitude: 0 };
+const FOOD_POISONING: CorpseEffect = CorpseEffect { status: StatusType::Poison, duration: 5, magnitude: 2 };
+
+/// Everything that leaves something worth eating. The undead, spellcasters
+/// and bosses leave nothing.
+pub fn all_corpses() -> Vec<CorpseDef> {
+    vec![
+        CorpseDef {
+            monster: "Rat", item_name: "Rat Corpse", nutrition: 80, cookable: true,
+            raw_risk: 0.5, sickness: DISEASE, boon: None,
+        },
+        CorpseDef {
+            monster: "Goblin", item_name: "Goblin Corpse", nutrition: 150, cookable: true,
+            raw_risk: 0.2, sickness: DISEASE, boon: None,
+        },
+        CorpseDef {
+            monster: "Goblin Archer", item_name: "Goblin Archer Corpse", nutrition: 120, cookable: true,
+            raw_risk: 0.2, sickness: DISEASE, boon: None,
+        },
+        CorpseDef {
+            monster: "Giant Spider", item_name: "Giant Spider Corpse", nutrition: 120, cookable: true,
+            raw_risk: 0.6, sickness: FOOD_POISONING, boon: None,
+        },
+        CorpseDef {
+            monster: "Orc", item_name: "Orc Corpse", nutrition: 250, cookable: true,
+            raw_risk: 0.2, sickness: DISEASE,
+            boon: Some(CorpseEffect { status: StatusType::Strengthened, duration: 30, magnitude: 0 }),
+        },
+        CorpseDef {
+            monster: "Cave Troll", item_name: "Cave Troll Corpse", nutrition: 400, cookable: true,
+            raw_risk: 0.2, sickness: DISEASE,
+            boon: Some(CorpseEffect { status: StatusType::Regenerating, duration: 10, magnitude: 2 }),
+        },
+        CorpseDef {
+            monster: "Vampire Bat", item_name: "Vampire Bat Corpse", nutrition: 60, cookable: true,
+            raw_risk: 0.5, sickness: DISEASE, boon: None,
+        },
+        CorpseDef {
+            monster: "Rust Monster", item_name: "Rust Monster Corpse", nutrition: 200, cookable: true,
+            raw_risk: 0.3, sickness: FOOD_POISONING, boon: None,
+        },
+        CorpseDef {
+            monster: "Mimic", item_name: "Mimic Corpse", nutrition: 200, cookable: true,
+            raw_risk: 0.3, sickness: FOOD_POISONING, boon: None,
+        },
+        CorpseDef {
+            monster: "Fire Elemental", item_name: "Fire Elemental Core", nutrition: 50, cookable: false,
+            raw_risk: 0.0, sickness: DISEASE,
+            boon: Some(CorpseEffect { status: StatusType::FireWard, duration: 100, magnitude: 50 }),
+        },
+        CorpseDef {
+            monster: "Ice Golem", item_name: "Ice Golem Core", nutrition: 50, cookable: false,
+            raw_risk: 0.0, sickness: DISEASE,
+            boon: Some(CorpseEffect { status: StatusType::FrostWard, duration: 100, magnitude: 50 }),
+        },
+    ]
+}
+
+/// The corpse a monster leaves, looking past elite prefixes such as
+/// "Brutal Orc".
+pub fn get_def(monster: &str) -> Option<CorpseDef> {
+    all_corpses()
+        .into_iter()
+        .filter(|d| monster == d.monster || monster.ends_with(&format!(" {}", d.monster)))
+        .max_by_key(|d| d.monster.len())
+}
+
+/// A fresh, raw corpse of `monster` lying at `pos`.
+pub fn create_corpse(monster: &str, pos: Position) -> Option<Entity> {
+    let def = get_def(monster)?;
+    Some(Entity {
+        id: next_id(),
+        name: def.item_name.to_string(),
+        position: pos,
+        glyph: 0x25, // '%'
+        render_order: RenderOrder::Item,
+        blocks_movement: false,
+        blocks_fov: false,
+        health: None,
+        combat: None,
+        ai: None,
+        inventory: None,
+        equipment: None,
+        item: Some(ItemProperties {
+            item_type: ItemType::Food,
+            slot: None,
+            power: 0,
+            speed_mod: 0,
+            effect: Some(ItemEffect::Corpse { monster: def.monster.to_string(), cooked: false }),
+            charges: None,
+            energy_cost: 100,
+            ammo_type: None,
+            ranged: None,
+            hunger_restore: def.nutrition,
+            enchant_level: 0,
+            identified: true,
+            resistances: Vec::new(),
+            cursed: false,
+            curse_known: false,
+            affixes: Vec::new(),
+            quantity: 1,
+            durability: 0,
+            max_durability: 0,
+        }),
+        status_effects: Vec::new(),
+        fov: None,
+        door: None,
+        trap: None,
+        stair: None,
+        loot_table: None,
+        flavor_text: None,
+        shop: None,
+        interactive: None,
+        elite: None,
+        resurrection_timer: None,
+    })
+}
+
+/// Whether an item is a raw corpse that can go over a fire.
+pub fn is_cookable(item: &Entity) -> bool {
+    match item.item.as_ref().and_then(|p| p.effect.as_ref()) {
+        Some(ItemEffect::Corpse { monster, cooked: false }) => get_def(monster).is_some_and(|d| d.cookable),
+        _ => false,
+    }
+}
+
+/// Turn one raw corpse into a cooked meal: safe to eat and more filling.
+pub fn cook(item: &mut Entity) {
+    let Some(props) = item.item.as_mut() else {
+        return;
+    };
+    if let Some(ItemEffect::Corpse { monster, cooked }) = props.effect.as_mut() {
+        item.name = format!("Cooked {}", monster);
+        *cooked = true;
+        props.hunger_restore += props.hunger_restore * COOKED_NUTRITION_BONUS / 100;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::enemies::{all_enemies, is_undead};
+
+    #[test]
+    fn corpses_belong_to_living_monsters() {
+        for def in all_corpses() {
+            assert!(all_enemies().iter().any(|t| t.name == def.monster), "no monster {}", def.monster);
+            assert!(!is_undead(def.monster));
+            assert!(def.nutrition > 0);
+        }
+        assert!(get_def("Skeleton").is_none());
+        assert!(get_def("Goblin King").is_none());
+        assert_eq!(get_def("Brutal Goblin Archer").map(|d| d.monster), Some("Goblin Archer"));
+        assert_eq!(get_def("Brutal Goblin").map(|d| d.monster), Some("Goblin"));
+    }
+
+    #[test]
+    fn cooking_makes_a_bigger_meal() {
+        let mut corpse = create_corpse("Orc", Position::new(0, 0)).unwrap();
+        assert!(is_cookable(&corpse));
+        cook(&mut corpse);
+        assert_eq!(corpse.name, "Cooked Orc");
+        assert_eq!(corpse.item.as_ref().unwrap().hunger_restore, 375);
+        assert!(!is_cookable(&corpse));
+
+        let core = create_corpse("Fire Elemental", Position::new(0, 0)).unwrap();
+        assert!(!is_cookable(&core));
+    }
+}
@@ -0,0 +1,175 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::entity::*;
+
+/// Favor is kept within this distance of zero.
+pub const FAVOR_CAP: i32 = 100;
+/// Favor a boon costs; praying with less (but not negative) favor goes unanswered.
+pub const BOON_COST: i32 = 30;
+/// Turns the patron needs between prayers.
+pub const PRAYER_TIMEOUT: u32 = 50;
+
+/// Deeds the gods take notice of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conduct {
+    SlayFoe,
+    SlayUndead,
+    /// Casting a spell, reading a scroll or zapping a wand.
+    UseMagic,
+    /// Raising or summoning the dead.
+    Necromancy,
+    /// Buying from a shopkeeper.
+    Purchase,
+    /// An offering at the patron's own altar.
+    Offering,
+    /// Gold given up at an altar.
+    Tithe(u32),
+    /// An offering at another god's altar.
+    RivalOffering,
+    /// Praying before the god is ready to listen.
+    Impatience,
+}
+
+pub struct DeityDef {
+    pub deity: Deity,
+    pub name: &'static str,
+    pub title: &'static str,
+    pub likes: &'static [&'static str],
+    pub dislikes: &'static [&'static str],
+    pub boon: &'static str,
+    pub wrath: &'static str,
+}
+
+pub const ALL_DEITIES: [Deity; 3] = [Deity::Solara, Deity::Aurum, Deity::Korr];
+
+pub fn get_def(deity: Deity) -> DeityDef {
+    match deity {
+        Deity::Solara => DeityDef {
+            deity,
+            name: "Solara",
+            title: "the Dawnbringer",
+            likes: &["Slaying the undead", "Offerings at her altars"],
+            dislikes: &["Raising the dead", "Offerings to other gods"],
+            boon: "Heals you fully and burns away harmful effects.",
+            wrath: "Blinds you with searing light.",
+        },
+        Deity::Aurum => DeityDef {
+            deity,
+            name: "Aurum",
+            title: "the Gilded",
+            likes: &["Tithes of gold at his altars"],
+            dislikes: &["Spending gold in shops", "Offerings to other gods"],
+            boon: "Showers you with gold.",
+            wrath: "Takes half your gold.",
+        },
+        Deity::Korr => DeityDef {
+            deity,
+            name: "Korr",
+            title: "the Iron Hand",
+            likes: &["Slaying foes", "Offerings at his altars"],
+            dislikes: &["Spells, scrolls and wands", "Offerings to other gods"],
+            boon: "Fills you with strength and speed.",
+            wrath: "Saps your strength.",
+        },
+    }
+}
+
+pub fn random_deity(rng: &mut impl Rng) -> Deity {
+    ALL_DEITIES[rng.gen_range(0..ALL_DEITIES.len())]
+}
+
+/// How much favor `deity` grants (or takes) for a deed.
+pub fn favor_for(deity: Deity, conduct: Conduct) -> i32 {
+    match (deity, conduct) {
+        (_, Conduct::Offering) => 10,
+        (_, Conduct::RivalOffering) => -15,
+        (_, Conduct::Impatience) => -5,
+        (Deity::Solara, Conduct::SlayUndead) => 4,
+        (Deity::Solara, Conduct::Necromancy) => -10,
+        (Deity::Aurum, Conduct::Tithe(gold)) => (gold / 4) as i32,
+        (Deity::Aurum, Conduct::Purchase) => -3,
+        (Deity::Korr, Conduct::SlayFoe | Conduct::SlayUndead) => 2,
+        (Deity::Korr, Conduct::UseMagic) => -4,
+        _ => 0,
+    }
+}
+
+/// How the patron feels about the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeityMood {
+    Wrathful,
+    Displeased,
+    Indifferent,
+    Pleased,
+    Exalted,
+}
+
+pub fn mood(favor: i32) -> DeityMood {
+    match favor {
+        i32::MIN..=-25 => DeityMood::Wrathful,
+        -24..=-1 => DeityMood::Displeased,
+        0..=29 => DeityMood::Indifferent,
+        30..=74 => DeityMood::Pleased,
+        _ => DeityMood::Exalted,
+    }
+}
+
+/// The patron and the player's standing, as shown in the HUD.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeityStatus {
+    pub deity: Deity,
+    pub name: String,
+    pub title: String,
+    pub favor: i32,
+    pub mood: DeityMood,
+    pub likes: Vec<String>,
+    pub dislikes: Vec<String>,
+    pub boon: String,
+    pub wrath: String,
+    /// Turns until the patron will hear another prayer.
+    pub prayer_timeout: u32,
+}
+
+pub fn status(deity: Deity, favor: i32, prayer_timeout: u32) -> DeityStatus {
+    let def = get_def(deity);
+    DeityStatus {
+        deity,
+        name: def.name.to_string(),
+        title: def.title.to_string(),
+        favor,
+        mood: mood(favor),
+        likes: def.likes.iter().map(|s| s.to_string()).collect(),
+        dislikes: def.dislikes.iter().map(|s| s.to_string()).collect(),
+        boon: def.boon.to_string(),
+        wrath: def.wrath.to_string(),
+        prayer_timeout,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gods_have_their_own_tastes() {
+        assert!(favor_for(Deity::Solara, Conduct::SlayUndead) > 0);
+        assert_eq!(favor_for(Deity::Solara, Conduct::SlayFoe), 0);
+        assert!(favor_for(Deity::Korr, Conduct::UseMagic) < 0);
+        assert_eq!(favor_for(Deity::Solara, Conduct::UseMagic), 0);
+        assert_eq!(favor_for(Deity::Aurum, Conduct::Tithe(40)), 10);
+        for deity in ALL_DEITIES {
+            assert!(favor_for(deity, Conduct::RivalOffering) < 0);
+            assert!(!get_def(deity).likes.is_empty());
+        }
+    }
+
+    #[test]
+    fn mood_follows_favor() {
+        assert_eq!(mood(-FAVOR_CAP), DeityMood::Wrathful);
+        assert_eq!(mood(-1), DeityMood::Displeased);
+        assert_eq!(mood(0), DeityMood::Indifferent);
+        assert_eq!(mood(BOON_COST), DeityMood::Pleased);
+        assert_eq!(mood(FAVOR_CAP), DeityMood::Exalted);
+    }
+}
@@ -2,8 +2,11 @@ use rand::Rng;
 use std::collections::HashSet;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+use crate::engine::affixes;
+use crate::engine::deities;
 use crate::engine::entity::*;
-use crate::engine::enemies::{all_enemies, boss_templates, apply_endless_scaling, get_boss_for_floor, get_enemy_pool, EnemySpecial};
+use crate::engine::bosses::BossDefinition;
+use crate::engine::enemies::{all_enemies, apply_endless_scaling, enemy_resistances, get_enemy_pool, EnemySpecial, EnemyTemplate};
 use crate::engine::items::all_items;
 use crate::engine::map::{Map, Room, RoomType, TileType};
 
@@ -21,6 +24,7 @@ fn map_special_to_on_hit(special: &Option<EnemySpecial>) -> Option<OnHitEffect>
         Some(EnemySpecial::ConfuseOnHit { duration }) => {
             Some(OnHitEffect::Confuse { duration: *duration })
         }
+        Some(EnemySpecial::CorrodeOnHit { amount }) => Some(OnHitEffect::Corrode { amount: *amount }),
         Some(EnemySpecial::LifeSteal) => Some(OnHitEffect::LifeSteal),
         Some(EnemySpecial::DrainMaxHp) => Some(OnHitEffect::DrainMaxHp),
         _ => None,
@@ -51,6 +55,7 @@ pub fn spawn_player(pos: Position) -> Entity {
             dodge_chance: 0.0,
             ranged: None,
             on_hit: None,
+            resistances: Vec::new(),
         }),
         ai: None,
         inventory: Some(Inventory::new(20)),
@@ -88,9 +93,13 @@ pub fn spawn_player_with_class(pos: Position, template: &crate::engine::classes:
             dodge_chance: template.dodge_chance,
             ranged: None,
             on_hit: None,
+            resistances: Vec::new(),
         }),
         ai: None,
-        inventory: Some(Inventory::new(20)),
+        inventory: Some(Inventory {
+            carry_capacity: template.carry_capacity,
+            ..Inventory::new(20)
+        }),
         equipment: Some(EquipmentSlots::empty()),
         item: None,
         status_effects: Vec::new(),
@@ -107,15 +116,13 @@ pub fn spawn_player_with_class(pos: Position, template: &crate::engine::classes:
     }
 }
 
-pub fn spawn_entities(map: &Map, floor: u32, rng: &mut impl Rng) -> Vec<Entity> {
+pub fn spawn_entities(map: &Map, floor: u32, boss: Option<&BossDefinition>, rng: &mut impl Rng) -> Vec<Entity> {
     let mut entities = Vec::new();
     let mut occupied: HashSet<Position> = HashSet::new();
 
     let enemy_pool = get_enemy_pool(floor);
     let all_enemy_templates = all_enemies();
-    let all_boss_templates = boss_templates();
     let all_item_templates = all_items();
-    let boss_name = get_boss_for_floor(floor);
 
     for room in &map.rooms {
         let positions = get_floor_positions(map, room);
@@ -128,23 +135,26 @@ pub fn spawn_entities(map: &Map, floor: u32, rng: &mut impl Rng) -> Vec<Entity>
                 // Place a health potion in the start room on floor 1
                 if floor == 1 {
                     if let Some(pos) = pick_free_pos(&positions, &occupied, rng) {
-                        entities.push(create_item("Health Potion", pos, &all_item_templates));
+                        entities.push(create_item("Health Potion", pos, &all_item_templates, floor, rng));
                         occupied.insert(pos);
                     }
                     if let Some(pos) = pick_free_pos(&positions, &occupied, rng) {
-                        entities.push(create_item("Dagger", pos, &all_item_templates));
+                        entities.push(create_item("Dagger", pos, &all_item_templates, floor, rng));
                         occupied.insert(pos);
                     }
                 }
+                // A campfire in the corner for cooking what you kill
+                if let Some(&pos) = positions.iter().find(|p| !occupied.contains(p)) {
+                    entities.push(create_interactable(InteractionType::Campfire, pos, None));
+                    occupied.insert(pos);
+                }
             }
             RoomType::Boss => {
                 // Boss + 1-2 minions
-                if let Some(name) = boss_name {
-                    if let Some(template) = all_boss_templates.iter().find(|t| t.name == name) {
-                        if let Some(pos) = pick_free_pos(&positions, &occupied, rng) {
-                            entities.push(create_enemy_from_template(template, pos, floor, rng));
-                            occupied.insert(pos);
-                        }
+                if let Some(definition) = boss {
+                    if let Some(pos) = pick_free_pos(&positions, &occupied, rng) {
+                        entities.push(create_boss(definition, pos));
+                        occupied.insert(pos);
                     }
                     // 1-2 minions
                     let minion_count = rng.gen_range(1..=2);
@@ -223,7 +233,7 @@ pub fn spawn_entities(map: &Map, floor: u32, rng: &mut impl Rng) -> Vec<Entity>
                     if roll < 0.33 {
                         entities.push(create_interactable(InteractionType::Fountain, pos, None));
                     } else if roll < 0.66 {
-                        entities.push(create_interactable(InteractionType::Altar, pos, None));
+                        entities.push(create_altar(deities::random_deity(rng), pos));
                     } else {
                         entities.push(create_interactable(InteractionType::Anvil, pos, None));
                     }
@@ -332,7 +342,7 @@ pub fn spawn_entities(map: &Map, floor: u32, rng: &mut impl Rng) -> Vec<Entity>
     }
 
     // 15% chance per non-boss floor: spawn an NPC ally (Prisoner)
-    if boss_name.is_none() && rng.gen::<f32>() < 0.15 {
+    if boss.is_none() && rng.gen::<f32>() < 0.15 {
         let normal_rooms: Vec<&Room> = map.rooms.iter()
             .filter(|r| r.room_type == RoomType::Normal)
             .collect();
@@ -346,14 +356,14 @@ pub fn spawn_entities(map: &Map, floor: u32, rng: &mut impl Rng) -> Vec<Entity>
     }
 
     // Place boss key on boss floors (in a non-boss, non-start room)
-    if boss_name.is_some() {
+    if boss.is_some() {
         let key_rooms: Vec<&Room> = map.rooms.iter()
             .filter(|r| r.room_type != RoomType::Boss && r.room_type != RoomType::Start)
             .collect();
         if let Some(key_room) = key_rooms.get(rng.gen_range(0..key_rooms.len().max(1))) {
             let positions = get_floor_positions(map, key_room);
             if let Some(pos) = pick_free_pos(&positions, &occupied, rng) {
-                entities.push(create_item("Boss Key", pos, &all_item_templates));
+                entities.push(create_item("Boss Key", pos, &all_item_templates, floor, rng));
                 occupied.insert(pos);
             }
         }
@@ -481,8 +491,47 @@ fn create_enemy_from_template(
             dodge_chance: 0.0,
             ranged: None,
             on_hit,
+            resistances: enemy_resistances(template.name),
         }),
         ai: Some(template.ai.clone()),
+        inventory: if template.uses_items { Some(Inventory::new(4)) } else { None },
+        equipment: if template.uses_items { Some(EquipmentSlots::empty()) } else { None },
+        item: None,
+        status_effects: Vec::new(),
+        fov: Some(FieldOfView::new(6)),
+        door: None,
+        trap: None,
+        stair: None,
+        loot_table: None,
+        flavor_text: None,
+        shop: None,
+        interactive: None,
+        elite: elite_prefix,
+        resurrection_timer: None,
+    }
+}
+
+fn create_boss(definition: &BossDefinition, pos: Position) -> Entity {
+    Entity {
+        id: next_id(),
+        name: definition.name.clone(),
+        position: pos,
+        glyph: definition.glyph,
+        render_order: RenderOrder::Enemy,
+        blocks_movement: true,
+        blocks_fov: false,
+        health: Some(Health::new(definition.hp)),
+        combat: Some(CombatStats {
+            base_attack: definition.attack,
+            base_defense: definition.defense,
+            base_speed: definition.speed,
+            crit_chance: definition.crit_chance,
+            dodge_chance: 0.0,
+            ranged: None,
+            on_hit: None,
+            resistances: definition.resistances.clone(),
+        }),
+        ai: Some(AIBehavior::Boss(BossPhase::Phase1)),
         inventory: None,
         equipment: None,
         item: None,
@@ -495,19 +544,24 @@ fn create_enemy_from_template(
         flavor_text: None,
         shop: None,
         interactive: None,
-        elite: elite_prefix,
+        elite: None,
         resurrection_timer: None,
     }
 }
 
-fn create_item(
+/// Build an item from its template. Equipment found deeper in the dungeon
+/// may roll a curse or, failing that, random affixes; floor 0 always yields
+/// the plain template.
+pub(crate) fn create_item(
     name: &str,
     pos: Position,
     templates: &[crate::engine::items::ItemTemplate],
+    floor: u32,
+    rng: &mut impl Rng,
 ) -> Entity {
     let t = templates.iter().find(|t| t.name == name)
         .unwrap_or_else(|| panic!("Item template '{}' not found", name));
-    Entity {
+    let mut item = Entity {
         id: next_id(),
         name: t.name.to_string(),
         position: pos,
@@ -520,20 +574,7 @@ fn create_item(
         ai: None,
         inventory: None,
         equipment: None,
-        item: Some(ItemProperties {
-            item_type: t.item_type,
-            slot: t.slot,
-            power: t.power,
-            speed_mod: t.speed_mod,
-            effect: t.effect.clone(),
-            charges: t.charges,
-            energy_cost: t.energy_cost,
-            ammo_type: t.ammo_type,
-            ranged: t.ranged,
-            hunger_restore: t.hunger_restore,
-            enchant_level: 0,
-            identified: true,
-        }),
+        item: Some(t.properties()),
         status_effects: Vec::new(),
         fov: None,
         door: None,
@@ -545,7 +586,14 @@ fn create_item(
         interactive: None,
         elite: None,
         resurrection_timer: None,
+    };
+    maybe_curse(&mut item, floor, rng);
+    if let Some(props) = item.item.as_mut() {
+        if let (Some(slot), false) = (props.slot, props.cursed) {
+            props.affixes = affixes::roll_affixes(slot, floor, rng);
+        }
     }
+    item
 }
 
 pub(crate) fn pick_weighted_item(
@@ -568,65 +616,62 @@ pub(crate) fn pick_weighted_item(
     for t in &eligible {
         let w = t.rarity.weight();
         if roll < w {
-            let entity = Entity {
-                id: next_id(),
-                name: t.name.to_string(),
-                position: Position::new(0, 0),
-                glyph: t.glyph,
-                render_order: RenderOrder::Item,
-                blocks_movement: false,
-                blocks_fov: false,
-                health: None,
-                combat: None,
-                ai: None,
-                inventory: None,
-                equipment: None,
-                item: Some(ItemProperties {
-                    item_type: t.item_type,
-                    slot: t.slot,
-                    power: t.power,
-                    speed_mod: t.speed_mod,
-                    effect: t.effect.clone(),
-                    charges: t.charges,
-                    energy_cost: t.energy_cost,
-                    ammo_type: t.ammo_type,
-                    ranged: t.ranged,
-                    hunger_restore: t.hunger_restore,
-                    enchant_level: 0,
-                    identified: true,
-                }),
-                status_effects: Vec::new(),
-                fov: None,
-                door: None,
-                trap: None,
-                stair: None,
-                loot_table: None,
-                flavor_text: None,
-                shop: None,
-                interactive: None,
-                elite: None,
-                resurrection_timer: None,
-            };
-            return Some(entity);
+            return Some(create_item(t.name, Position::new(0, 0), templates, floor, rng));
         }
         roll -= w;
     }
     None
 }
 
+/// Roll whether a piece of floor equipment is cursed. Deeper floors curse
+/// more often and more heavily: -1 early, down to -3 from floor 8.
+fn maybe_curse(item: &mut Entity, floor: u32, rng: &mut impl Rng) {
+    let props = match item.item.as_mut() {
+        Some(p) if p.slot.is_some() => p,
+        _ => return,
+    };
+    let chance = (0.06 + floor as f32 * 0.01).min(0.2);
+    if rng.gen::<f32>() >= chance {
+        return;
+    }
+    let penalty = rng.gen_range(1..=(1 + floor as i32 / 4).min(3));
+    props.enchant_level = -penalty;
+    props.power -= penalty;
+    props.cursed = true;
+}
+
 fn generate_shop_inventory(
     floor: u32,
     rng: &mut impl Rng,
     templates: &[crate::engine::items::ItemTemplate],
 ) -> Vec<ShopItem> {
+    let count = rng.gen_range(4..=6);
+    let mut items = Vec::new();
+    stock_shop_items(&mut items, count, floor, rng, templates);
+    items
+}
+
+/// Add up to `extra` items the shop doesn't already sell.
+pub fn add_shop_stock(shop: &mut ShopInventory, extra: usize, floor: u32, rng: &mut impl Rng) {
+    let templates = all_items();
+    let count = shop.items.len() + extra;
+    stock_shop_items(&mut shop.items, count, floor, rng, &templates);
+}
+
+fn stock_shop_items(
+    items: &mut Vec<ShopItem>,
+    count: usize,
+    floor: u32,
+    rng: &mut impl Rng,
+    templates: &[crate::engine::items::ItemTemplate],
+) {
     let eligible: Vec<&crate::engine::items::ItemTemplate> = templates
         .iter()
         .filter(|t| t.min_floor <= floor + 1 && t.item_type != ItemType::Key && t.item_type != ItemType::Projectile)
         .collect();
 
-    let count = rng.gen_range(4..=6).min(eligible.len());
-    let mut items = Vec::new();
-    let mut used_names: HashSet<String> = HashSet::new();
+    let count = count.min(items.len() + eligible.len());
+    let mut used_names: HashSet<String> = items.iter().map(|i| i.name.clone()).collect();
 
     for _ in 0..count * 3 {
         if items.len() >= count {
@@ -637,23 +682,26 @@ fn generate_shop_inventory(
         if used_names.contains(t.name) {
             continue;
         }
+        // Shopkeepers stock the occasional magic item, priced to match
+        let affixes = t.slot
+            .map(|slot| affixes::roll_affixes(slot, floor, rng))
+            .unwrap_or_default();
         let price = match t.rarity {
             crate::engine::items::Rarity::Common => 5,
             crate::engine::items::Rarity::Uncommon => 12,
             crate::engine::items::Rarity::Rare => 25,
             crate::engine::items::Rarity::VeryRare => 50,
-        } + floor / 2;
+        } + floor / 2 + affixes::price_bonus(&affixes);
 
         items.push(ShopItem {
             name: t.name.to_string(),
             price,
             item_type: t.item_type,
             slot: t.slot,
+            affixes,
         });
         used_names.insert(t.name.to_string());
     }
-
-    items
 }
 
 fn create_shopkeeper(pos: Position, shop_items: Vec<ShopItem>) -> Entity {
@@ -700,12 +748,13 @@ fn create_interactable(
         InteractionType::Altar => ("Altar", 0x2B, false),       // '+'
         InteractionType::Chest => ("Chest", 0x3D, false),       // '='
         InteractionType::Anvil => ("Anvil", 0x26, false),       // '&'
+        InteractionType::Campfire => ("Campfire", 0x2A, false), // '*'
     };
 
     let uses = match interaction_type {
         InteractionType::Fountain | InteractionType::Altar | InteractionType::Chest => Some(1),
         InteractionType::Barrel => Some(1),
-        InteractionType::Lever | InteractionType::Anvil => None,
+        InteractionType::Lever | InteractionType::Anvil | InteractionType::Campfire => None,
     };
 
     Entity {
@@ -735,12 +784,22 @@ fn create_interactable(
             uses_remaining: uses,
             activated: false,
             contained_items: contained_items.unwrap_or_default(),
+            deity: None,
         }),
         elite: None,
         resurrection_timer: None,
     }
 }
 
+fn create_altar(deity: Deity, pos: Position) -> Entity {
+    let mut altar = create_interactable(InteractionType::Altar, pos, None);
+    altar.name = format!("Altar of {}", deities::get_def(deity).name);
+    if let Some(ref mut inter) = altar.interactive {
+        inter.deity = Some(deity);
+    }
+    altar
+}
+
 fn create_ally(rng: &mut impl Rng, pos: Position) -> Entity {
     // Three ally variants: Sellsword (melee), Healer (low atk), Scout (ranged-ish)
     let variant = rng.gen_range(0..3);
@@ -767,6 +826,7 @@ fn create_ally(rng: &mut impl Rng, pos: Position) -> Entity {
             dodge_chance: 0.0,
             ranged: None,
             on_hit: None,
+            resistances: Vec::new(),
         }),
         ai: Some(AIBehavior::Ally { follow_distance: 3 }),
         inventory: None,
@@ -785,3 +845,83 @@ fn create_ally(rng: &mut impl Rng, pos: Position) -> Entity {
         resurrection_timer: None,
     }
 }
+
+/// A plain (non-elite) copy of `template` scaled for `floor`, as summoned
+/// mid-fight by bosses and spells.
+pub fn create_minion(template: &EnemyTemplate, pos: Position, floor: u32) -> Entity {
+    let (hp, attack, defense) = apply_endless_scaling(template, floor);
+    Entity {
+        id: next_id(),
+        name: template.name.to_string(),
+        position: pos,
+        glyph: template.glyph,
+        render_order: RenderOrder::Enemy,
+        blocks_movement: true,
+        blocks_fov: false,
+        health: Some(Health::new(hp)),
+        combat: Some(CombatStats {
+            base_attack: attack,
+            base_defense: defense,
+            base_speed: template.speed,
+            crit_chance: template.crit_chance,
+            dodge_chance: 0.0,
+            ranged: None, // Ranged behavior is handled by AI, not stats
+            on_hit: None,
+            resistances: enemy_resistances(template.name),
+        }),
+        ai: Some(template.ai.clone()),
+        inventory: None,
+        equipment: None,
+        item: None,
+        status_effects: Vec::new(),
+        fov: Some(FieldOfView::new(6)),
+        door: None,
+        trap: None,
+        stair: None,
+        loot_table: None,
+        flavor_text: None,
+        shop: None,
+        interactive: None,
+        elite: None,
+        resurrection_timer: None,
+    }
+}
+
+/// The Ranger's wolf, which follows its master from floor to floor.
+pub fn create_companion(pos: Position) -> Entity {
+    Entity {
+        id: next_id(),
+        name: "Wolf".to_string(),
+        position: pos,
+        glyph: 0x77, // 'w'
+        render_order: RenderOrder::Enemy,
+        blocks_movement: true,
+        blocks_fov: false,
+        health: Some(Health::new(25)),
+        combat: Some(CombatStats {
+            base_attack: 5,
+            base_defense: 1,
+            base_speed: 120,
+            crit_chance: 0.05,
+            dodge_chance: 0.05,
+            ranged: None,
+            on_hit: None,
+            resistances: Vec::new(),
+        }),
+        ai: Some(AIBehavior::Ally { follow_distance: 2 }),
+        inventory: None,
+        equipment: None,
+        item: None,
+        status_effects: Vec::new(),
+        fov: Some(FieldOfView::new(8)),
+        door: None,
+        trap: None,
+        stair: None,
+        loot_table: None,
+        flavor_text: Some("Your loyal wolf. It hunts at your side.".to_string()),
+        shop: None,
+        interactive: None,
+        elite: None,
+        resurrection_timer: None,
+    }
+}
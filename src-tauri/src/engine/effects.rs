@@ -23,6 +23,20 @@ pub fn apply_status(entity: &mut Entity, effect_type: StatusType, duration: u32,
     });
 }
 
+/// Keep the player's Hunger status in step with the hunger clock: present
+/// from Hungry on, gone once they have eaten.
+pub fn sync_hunger(entity: &mut Entity, level: i32) {
+    entity.status_effects.retain(|s| s.effect_type != StatusType::Hunger);
+    if HungerState::for_level(level) >= HungerState::Hungry {
+        entity.status_effects.push(StatusEffect {
+            effect_type: StatusType::Hunger,
+            duration: 1,
+            magnitude: level,
+            source: "hunger".to_string(),
+        });
+    }
+}
+
 /// Remove all negative status effects (used by Antidote/CureStatus).
 pub fn cure_negative_effects(entity: &mut Entity) {
     entity.status_effects.retain(|s| !s.effect_type.is_negative());
@@ -58,7 +72,7 @@ pub fn tick_effects(entity: &mut Entity) -> EffectTickResult {
 
     // Decrement durations
     for effect in &mut entity.status_effects {
-        if effect.duration > 0 {
+        if effect.duration > 0 && effect.effect_type.counts_down() {
             effect.duration -= 1;
             if effect.duration == 0 {
                 result.expired.push(effect.effect_type);
@@ -154,6 +168,7 @@ mod tests {
                 dodge_chance: 0.0,
                 ranged: None,
                 on_hit: None,
+                resistances: Vec::new(),
             }),
             ai: Some(AIBehavior::Melee),
             inventory: None,
@@ -10,6 +10,8 @@ pub struct EnemyTemplate {
     pub crit_chance: f32,
     pub ai: AIBehavior,
     pub special: Option<EnemySpecial>,
+    /// Picks up floor items, equips gear, drinks healing potions and zaps wands.
+    pub uses_items: bool,
     pub min_floor: u32,
 }
 
@@ -18,6 +20,7 @@ pub enum EnemySpecial {
     BurningOnHit { damage: i32, duration: u32 },
     SlowOnHit { magnitude: i32, duration: u32 },
     ConfuseOnHit { duration: u32 },
+    CorrodeOnHit { amount: u32 },
     LifeSteal,
     PhaseThroughWalls,
     DrainMaxHp,
@@ -35,55 +38,66 @@ pub fn all_enemies() -> Vec<EnemyTemplate> {
         // Floors 1-3: The Dungeon
         EnemyTemplate {
             name: "Rat", glyph: 0x72, hp: 8, attack: 2, defense: 0, speed: 120,
-            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, min_floor: 1,
+            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, uses_items: false, min_floor: 1,
         },
         EnemyTemplate {
             name: "Goblin", glyph: 0x67, hp: 15, attack: 4, defense: 1, speed: 100,
-            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, min_floor: 1,
+            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, uses_items: true, min_floor: 1,
         },
         EnemyTemplate {
             name: "Goblin Archer", glyph: 0x47, hp: 10, attack: 3, defense: 0, speed: 100,
             crit_chance: 0.05,
             ai: AIBehavior::Ranged { range: 5, preferred_distance: 3 },
-            special: None, min_floor: 2,
+            special: None, uses_items: true, min_floor: 2,
         },
         EnemyTemplate {
             name: "Skeleton", glyph: 0x73, hp: 18, attack: 5, defense: 3, speed: 90,
-            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, min_floor: 3,
+            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, uses_items: false, min_floor: 3,
         },
         EnemyTemplate {
             name: "Giant Spider", glyph: 0x53, hp: 12, attack: 3, defense: 1, speed: 110,
             crit_chance: 0.05, ai: AIBehavior::Melee,
             special: Some(EnemySpecial::PoisonOnHit { damage: 2, duration: 3 }),
+            uses_items: false,
             min_floor: 3,
         },
 
         // Floors 4-6: The Caves
         EnemyTemplate {
             name: "Orc", glyph: 0x6F, hp: 30, attack: 7, defense: 3, speed: 90,
-            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, min_floor: 4,
+            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, uses_items: true, min_floor: 4,
         },
         EnemyTemplate {
             name: "Dark Mage", glyph: 0x4D, hp: 15, attack: 2, defense: 1, speed: 100,
             crit_chance: 0.05,
             ai: AIBehavior::Ranged { range: 6, preferred_distance: 4 },
             special: Some(EnemySpecial::ConfuseOnHit { duration: 3 }),
+            uses_items: true,
             min_floor: 4,
         },
         EnemyTemplate {
             name: "Cave Troll", glyph: 0x54, hp: 50, attack: 10, defense: 5, speed: 70,
-            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, min_floor: 5,
+            crit_chance: 0.05, ai: AIBehavior::Melee, special: None, uses_items: false, min_floor: 5,
         },
         EnemyTemplate {
             name: "Vampire Bat", glyph: 0x62, hp: 12, attack: 4, defense: 0, speed: 130,
             crit_chance: 0.05, ai: AIBehavior::Melee,
             special: Some(EnemySpecial::LifeSteal),
+            uses_items: false,
+            min_floor: 4,
+        },
+        EnemyTemplate {
+            name: "Rust Monster", glyph: 0x52, hp: 22, attack: 5, defense: 4, speed: 100,
+            crit_chance: 0.05, ai: AIBehavior::Melee,
+            special: Some(EnemySpecial::CorrodeOnHit { amount: 8 }),
+            uses_items: false,
             min_floor: 4,
         },
         EnemyTemplate {
             name: "Mimic", glyph: 0x6D, hp: 25, attack: 8, defense: 3, speed: 100,
             crit_chance: 0.05, ai: AIBehavior::Passive,
             special: Some(EnemySpecial::Disguised),
+            uses_items: false,
             min_floor: 5,
         },
 
@@ -92,24 +106,28 @@ pub fn all_enemies() -> Vec<EnemyTemplate> {
             name: "Wraith", glyph: 0x57, hp: 20, attack: 8, defense: 2, speed: 110,
             crit_chance: 0.05, ai: AIBehavior::Melee,
             special: Some(EnemySpecial::DrainMaxHp),
+            uses_items: false,
             min_floor: 7,
         },
         EnemyTemplate {
             name: "Fire Elemental", glyph: 0x46, hp: 35, attack: 9, defense: 4, speed: 100,
             crit_chance: 0.05, ai: AIBehavior::Melee,
             special: Some(EnemySpecial::BurningOnHit { damage: 3, duration: 3 }),
+            uses_items: false,
             min_floor: 7,
         },
         EnemyTemplate {
             name: "Ice Golem", glyph: 0x49, hp: 60, attack: 7, defense: 8, speed: 60,
             crit_chance: 0.05, ai: AIBehavior::Melee,
             special: Some(EnemySpecial::SlowOnHit { magnitude: 30, duration: 2 }),
+            uses_items: false,
             min_floor: 7,
         },
         EnemyTemplate {
             name: "Shadow", glyph: 0x5A, hp: 15, attack: 12, defense: 1, speed: 120,
             crit_chance: 0.30, ai: AIBehavior::Melee,
             special: Some(EnemySpecial::Invisible),
+            uses_items: false,
             min_floor: 8,
         },
         EnemyTemplate {
@@ -117,6 +135,7 @@ pub fn all_enemies() -> Vec<EnemyTemplate> {
             crit_chance: 0.05,
             ai: AIBehavior::Ranged { range: 6, preferred_distance: 5 },
             special: Some(EnemySpecial::SummonSkeleton { interval: 5 }),
+            uses_items: true,
             min_floor: 8,
         },
     ]
@@ -129,6 +148,7 @@ pub fn boss_templates() -> Vec<EnemyTemplate> {
             crit_chance: 0.10,
             ai: AIBehavior::Boss(BossPhase::Phase1),
             special: Some(EnemySpecial::SummonSkeleton { interval: 4 }),
+            uses_items: false,
             min_floor: 3,
         },
         EnemyTemplate {
@@ -136,6 +156,7 @@ pub fn boss_templates() -> Vec<EnemyTemplate> {
             crit_chance: 0.10,
             ai: AIBehavior::Boss(BossPhase::Phase1),
             special: Some(EnemySpecial::Cleave),
+            uses_items: false,
             min_floor: 6,
         },
         EnemyTemplate {
@@ -143,6 +164,7 @@ pub fn boss_templates() -> Vec<EnemyTemplate> {
             crit_chance: 0.15,
             ai: AIBehavior::Boss(BossPhase::Phase1),
             special: Some(EnemySpecial::TeleportWhenAdjacent),
+            uses_items: false,
             min_floor: 10,
         },
     ]
@@ -153,9 +175,9 @@ pub fn get_enemy_pool(floor: u32) -> Vec<&'static str> {
         1 => vec!["Rat", "Goblin"],
         2 => vec!["Rat", "Goblin", "Goblin Archer"],
         3 => vec!["Goblin", "Goblin Archer", "Skeleton", "Giant Spider"],
-        4 => vec!["Orc", "Dark Mage", "Vampire Bat"],
-        5 => vec!["Orc", "Dark Mage", "Cave Troll", "Vampire Bat", "Mimic"],
-        6 => vec!["Orc", "Dark Mage", "Cave Troll", "Vampire Bat", "Mimic"],
+        4 => vec!["Orc", "Dark Mage", "Vampire Bat", "Rust Monster"],
+        5 => vec!["Orc", "Dark Mage", "Cave Troll", "Vampire Bat", "Rust Monster", "Mimic"],
+        6 => vec!["Orc", "Dark Mage", "Cave Troll", "Vampire Bat", "Rust Monster", "Mimic"],
         7 => vec!["Wraith", "Fire Elemental", "Ice Golem"],
         8 => vec!["Wraith", "Fire Elemental", "Ice Golem", "Shadow", "Necromancer"],
         9 => vec!["Wraith", "Fire Elemental", "Ice Golem", "Shadow", "Necromancer"],
@@ -164,24 +186,40 @@ pub fn get_enemy_pool(floor: u32) -> Vec<&'static str> {
     }
 }
 
+/// Scripted boss for the fixed boss floors. Endless boss floors get a
+/// procedurally generated boss from `bosses::generate_endless_boss` instead.
 pub fn get_boss_for_floor(floor: u32) -> Option<&'static str> {
     match floor {
         3 => Some("Goblin King"),
         6 => Some("Troll Warlord"),
         10 => Some("The Lich"),
-        f if f > 10 && f % 5 == 0 => {
-            // Cycle bosses for endless mode
-            let boss_idx = ((f - 15) / 5) % 3;
-            match boss_idx {
-                0 => Some("Goblin King"),
-                1 => Some("Troll Warlord"),
-                _ => Some("The Lich"),
-            }
-        }
         _ => None,
     }
 }
 
+/// Innate resistances and vulnerabilities. Negative percentages are
+/// vulnerabilities (the creature takes extra damage of that type).
+pub fn enemy_resistances(name: &str) -> Vec<Resistance> {
+    use DamageType::*;
+    let table: &[(DamageType, i32)] = match name {
+        "Skeleton" => &[(Poison, 100), (Necrotic, 50), (Holy, -50)],
+        "Giant Spider" => &[(Poison, 100)],
+        "Cave Troll" | "Troll Warlord" => &[(Fire, -50)],
+        "Wraith" | "Shadow" => &[(Poison, 100), (Necrotic, 100), (Physical, 25), (Holy, -50)],
+        "Fire Elemental" => &[(Fire, 100), (Poison, 100), (Cold, -50)],
+        "Ice Golem" => &[(Cold, 100), (Poison, 100), (Fire, -100)],
+        "Necromancer" => &[(Necrotic, 50)],
+        "The Lich" => &[(Poison, 100), (Necrotic, 100), (Cold, 50), (Holy, -50)],
+        _ => &[],
+    };
+    table.iter().map(|&(damage_type, percent)| Resistance::new(damage_type, percent)).collect()
+}
+
+/// Undead monsters, which clerics can turn and holy damage burns.
+pub fn is_undead(name: &str) -> bool {
+    matches!(name, "Skeleton" | "Wraith" | "Shadow" | "The Lich")
+}
+
 pub fn apply_endless_scaling(template: &EnemyTemplate, floor: u32) -> (i32, i32, i32) {
     if floor <= 10 {
         return (template.hp, template.attack, template.defense);
@@ -199,9 +237,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn all_enemies_returns_15() {
+    fn all_enemies_returns_16() {
         let enemies = all_enemies();
-        assert_eq!(enemies.len(), 15);
+        assert_eq!(enemies.len(), 16);
     }
 
     #[test]
@@ -244,6 +282,7 @@ mod tests {
         assert_eq!(get_boss_for_floor(10), Some("The Lich"));
         assert_eq!(get_boss_for_floor(1), None);
         assert_eq!(get_boss_for_floor(5), None);
+        assert_eq!(get_boss_for_floor(15), None);
     }
 
     #[test]
@@ -263,6 +302,29 @@ mod tests {
         assert!(atk > template.attack, "Attack should scale up");
     }
 
+    #[test]
+    fn item_users_are_not_bosses() {
+        assert!(all_enemies().iter().any(|e| e.uses_items));
+        assert!(boss_templates().iter().all(|b| !b.uses_items));
+    }
+
+    #[test]
+    fn resistance_table_matches_roster() {
+        let names: Vec<&str> = all_enemies()
+            .iter()
+            .chain(boss_templates().iter())
+            .map(|e| e.name)
+            .collect();
+        let golem = enemy_resistances("Ice Golem");
+        assert!(golem.contains(&Resistance::new(DamageType::Fire, -100)));
+        assert!(golem.contains(&Resistance::new(DamageType::Cold, 100)));
+        for name in ["Skeleton", "Wraith", "The Lich"] {
+            assert!(names.contains(&name));
+            assert!(enemy_resistances(name).contains(&Resistance::new(DamageType::Poison, 100)));
+        }
+        assert!(enemy_resistances("Rat").is_empty());
+    }
+
     #[test]
     fn unique_enemy_names() {
         let enemies = all_enemies();
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+use super::deities::DeityStatus;
+use super::subclasses::SubclassView;
+use super::talents::TalentView;
+
 pub type EntityId = u32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -131,6 +135,69 @@ pub struct CombatStats {
     pub dodge_chance: f32,
     pub ranged: Option<RangedStats>,
     pub on_hit: Option<OnHitEffect>,
+    #[serde(default)]
+    pub resistances: Vec<Resistance>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Fire,
+    Cold,
+    Poison,
+    Lightning,
+    Necrotic,
+    Holy,
+}
+
+impl DamageType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Physical => "physical",
+            DamageType::Fire => "fire",
+            DamageType::Cold => "cold",
+            DamageType::Poison => "poison",
+            DamageType::Lightning => "lightning",
+            DamageType::Necrotic => "necrotic",
+            DamageType::Holy => "holy",
+        }
+    }
+
+    pub fn is_elemental(&self) -> bool {
+        matches!(self, DamageType::Fire | DamageType::Cold | DamageType::Lightning)
+    }
+}
+
+/// Percent damage reduction against one damage type: 100 = immune,
+/// 50 = half damage, -100 = double damage (vulnerable).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Resistance {
+    pub damage_type: DamageType,
+    pub percent: i32,
+}
+
+impl Resistance {
+    pub fn new(damage_type: DamageType, percent: i32) -> Self {
+        Self { damage_type, percent }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AffixKind {
+    Haste,
+    Vampiric,
+    Keen,
+    Flaming,
+    Warding,
+}
+
+/// A random magic property rolled onto equipment. Tier (1-3) scales its
+/// strength and rises with floor depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Affix {
+    pub kind: AffixKind,
+    pub tier: u8,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,6 +208,8 @@ pub enum OnHitEffect {
     Confuse { duration: u32 },
     LifeSteal,
     DrainMaxHp,
+    /// Eats away at the target's weapon and armor.
+    Corrode { amount: u32 },
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -174,6 +243,9 @@ pub enum BossPhase {
 pub struct Inventory {
     pub items: Vec<Entity>,
     pub max_size: usize,
+    /// Weight that can be carried without slowing down; 0 means no limit.
+    #[serde(default)]
+    pub carry_capacity: u32,
 }
 
 impl Inventory {
@@ -181,6 +253,7 @@ impl Inventory {
         Self {
             items: Vec::new(),
             max_size,
+            carry_capacity: 0,
         }
     }
 
@@ -258,6 +331,65 @@ pub struct ItemProperties {
     pub hunger_restore: i32,
     pub enchant_level: i32,
     pub identified: bool,
+    #[serde(default)]
+    pub resistances: Vec<Resistance>,
+    /// Cursed gear carries a negative enchantment and can't be taken off.
+    #[serde(default)]
+    pub cursed: bool,
+    /// Whether the player has discovered the curse (by wearing the item).
+    #[serde(default)]
+    pub curse_known: bool,
+    #[serde(default)]
+    pub affixes: Vec<Affix>,
+    /// Units in this stack. Always 1 for items that don't stack.
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+    /// Remaining wear before the item breaks. Ignored when `max_durability`
+    /// is 0 (jewelry, consumables, gear from older saves).
+    #[serde(default)]
+    pub durability: u32,
+    #[serde(default)]
+    pub max_durability: u32,
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+impl ItemProperties {
+    /// Lift a curse, undoing its negative enchantment. Returns false if the
+    /// item wasn't cursed.
+    pub fn remove_curse(&mut self) -> bool {
+        if !self.cursed {
+            return false;
+        }
+        if self.enchant_level < 0 {
+            self.power -= self.enchant_level;
+            self.enchant_level = 0;
+        }
+        self.cursed = false;
+        self.curse_known = false;
+        true
+    }
+
+    /// Broken gear still occupies its slot but grants none of its bonuses.
+    pub fn is_broken(&self) -> bool {
+        self.max_durability > 0 && self.durability == 0
+    }
+
+    /// Monster remains are left for the player to eat, not for monsters to loot.
+    pub fn is_corpse(&self) -> bool {
+        matches!(self.effect, Some(ItemEffect::Corpse { .. }))
+    }
+
+    /// Wear the item down by `amount`. Returns true if this broke it.
+    pub fn wear(&mut self, amount: u32) -> bool {
+        if self.max_durability == 0 || self.durability == 0 {
+            return false;
+        }
+        self.durability = self.durability.saturating_sub(amount);
+        self.durability == 0
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -273,6 +405,10 @@ pub enum ItemType {
     Key,
     Food,
     Projectile,
+    /// Crafting ingredients such as herbs, flasks and feathers.
+    Material,
+    /// Carried containers that take the weight off one kind of item.
+    Bag,
 }
 
 impl ItemType {
@@ -280,6 +416,11 @@ impl ItemType {
         matches!(self, ItemType::Potion | ItemType::Scroll | ItemType::Food)
     }
 
+    /// Items that share an inventory slot with others of the same kind.
+    pub fn is_stackable(&self) -> bool {
+        self.is_consumable() || matches!(self, ItemType::Projectile | ItemType::Material)
+    }
+
     pub fn is_equipment(&self) -> bool {
         matches!(
             self,
@@ -302,13 +443,29 @@ pub enum AmmoType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ItemEffect {
     Heal(i32),
-    DamageArea { damage: i32, radius: i32 },
+    DamageArea {
+        damage: i32,
+        radius: i32,
+        #[serde(default)]
+        damage_type: DamageType,
+    },
     ApplyStatus { effect: StatusType, duration: u32 },
     RevealMap,
     RevealSecrets,
     Teleport,
     CureStatus,
-    RangedAttack { damage: i32, status: Option<(StatusType, u32)> },
+    /// Reveal the true nature of one unidentified item in the pack.
+    Identify,
+    /// Lift the curse from every cursed item the player carries.
+    RemoveCurse,
+    RangedAttack {
+        damage: i32,
+        status: Option<(StatusType, u32)>,
+        #[serde(default)]
+        damage_type: DamageType,
+    },
+    /// Remains of a slain monster. See `corpses` for what eating one does.
+    Corpse { monster: String, cooked: bool },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -378,6 +535,17 @@ pub enum StatusType {
     Slowed,
     Shielded,
     Invisible,
+    /// Drawn toward thrown food instead of hunting the player.
+    Distracted,
+    /// Sick from tainted meat: weaker in a fight and unable to heal by resting.
+    Diseased,
+    /// Resists fire by `magnitude` percent.
+    FireWard,
+    /// Resists cold by `magnitude` percent.
+    FrostWard,
+    /// Worn down by the hunger clock; `magnitude` is the clock reading. Lasts
+    /// until the player eats rather than counting down.
+    Hunger,
 }
 
 impl StatusType {
@@ -391,8 +559,15 @@ impl StatusType {
                 | StatusType::Weakened
                 | StatusType::Blinded
                 | StatusType::Slowed
+                | StatusType::Distracted
+                | StatusType::Diseased
         )
     }
+
+    /// Hunger follows the hunger clock instead of a duration.
+    pub fn counts_down(&self) -> bool {
+        *self != StatusType::Hunger
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -418,6 +593,8 @@ pub struct ShopItem {
     pub price: u32,
     pub item_type: ItemType,
     pub slot: Option<EquipSlot>,
+    #[serde(default)]
+    pub affixes: Vec<Affix>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -442,6 +619,9 @@ pub struct Interactive {
     pub activated: bool,
     /// Items contained in chests
     pub contained_items: Vec<String>,
+    /// The god an altar is dedicated to.
+    #[serde(default)]
+    pub deity: Option<Deity>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -452,6 +632,16 @@ pub enum InteractionType {
     Altar,
     Chest,
     Anvil,
+    /// Cooks raw corpses.
+    Campfire,
+}
+
+/// Gods whose altars appear in shrines. See `deities` for their tastes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Deity {
+    Solara,
+    Aurum,
+    Korr,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -497,13 +687,26 @@ pub struct PlayerAction {
 pub enum PlayerActionType {
     Move(Direction),
     Wait,
+    /// Wait up to `turns` turns, stopping if anything happens.
+    Rest { turns: u32 },
+    /// Rest until HP and mana are full or something interrupts.
+    RestUntilHealed,
     PickUp,
     UseStairs,
     UseItem(u32),
     DropItem(u32),
+    /// Drop part of a stack.
+    DropItems { index: u32, count: u32 },
     EquipItem(u32),
     UnequipSlot(EquipSlot),
-    LevelUpChoice(LevelUpChoice),
+    /// Spend a talent point on a node of the class talent tree.
+    LearnTalent { talent_id: String },
+    /// Pick a specialization once the player reaches the subclass level.
+    ChooseSubclass { subclass: Subclass },
+    /// Ask the patron god for a boon.
+    Pray,
+    /// Offer gold rather than an item at an adjacent altar of Aurum.
+    PayTithe,
     ClickMove { x: i32, y: i32 },
     AutoExplore,
     RangedAttack { target_id: u32 },
@@ -512,20 +715,21 @@ pub enum PlayerActionType {
     Interact,
     UseAbility { ability_id: String, target: Option<Position> },
     Craft { weapon_idx: u32, scroll_idx: u32 },
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum LevelUpChoice {
-    MaxHp,
-    Attack,
-    Defense,
-    Speed,
-    Cleave,
-    Fortify,
-    Backstab,
-    Evasion,
-    SpellPower,
-    ManaRegen,
+    /// Throw one of an inventory item at a tile.
+    Throw { item_idx: u32, target: Position },
+    /// Restore an item's durability at an anvil.
+    Repair { item_idx: u32 },
+    /// Break an item down into scrap at an anvil.
+    Salvage { item_idx: u32 },
+    /// Make a recipe from the recipe book. `target_idx` names the pack item
+    /// a recipe works on, such as the armor being reinforced.
+    CraftRecipe { recipe_id: String, target_idx: Option<u32> },
+    /// Cook a raw corpse beside a campfire or in Inferno heat.
+    Cook { item_idx: u32 },
+    /// Give a pack item to an adjacent altar.
+    Offer { item_idx: u32 },
+    /// Read a Scroll of Identify on a chosen pack item.
+    IdentifyItem { scroll_idx: u32, target_idx: u32 },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -533,6 +737,9 @@ pub enum PlayerClass {
     Warrior,
     Rogue,
     Mage,
+    Ranger,
+    Cleric,
+    Necromancer,
 }
 
 impl Default for PlayerClass {
@@ -541,6 +748,31 @@ impl Default for PlayerClass {
     }
 }
 
+/// Life before the crypt, picked at character creation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Background {
+    Soldier,
+    Scholar,
+    Thief,
+}
+
+/// Specialization chosen at `subclasses::SUBCLASS_LEVEL`, two per class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Subclass {
+    Berserker,
+    Guardian,
+    Assassin,
+    Trickster,
+    Pyromancer,
+    Cryomancer,
+    Beastmaster,
+    Sharpshooter,
+    Crusader,
+    Oracle,
+    Lich,
+    Bonelord,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ElitePrefix {
     Frenzied,
@@ -555,6 +787,8 @@ pub enum RunModifier {
     Marathon,
     Pacifist,
     Cursed,
+    /// Hunger drains twice as fast.
+    Famine,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -567,12 +801,21 @@ pub enum AutoExploreInterrupt {
     NothingReachable,
 }
 
+/// Why a rest ended before its time was up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestInterrupt {
+    EnemySpotted,
+    TookDamage,
+    Hungry,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TurnResult {
     pub state: GameState,
     pub events: Vec<GameEvent>,
     pub game_over: Option<GameOverInfo>,
     pub auto_explore_interrupt: Option<AutoExploreInterrupt>,
+    pub rest_interrupt: Option<RestInterrupt>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -587,7 +830,20 @@ pub struct GameState {
     pub pending_level_up: bool,
     pub biome: Biome,
     pub seed: u64,
-    pub level_up_choices: Vec<LevelUpChoice>,
+    /// Unspent talent points.
+    pub talent_points: u32,
+    pub talent_tree: Vec<TalentView>,
+    /// Specializations on offer; empty unless the choice is due.
+    pub subclass_choices: Vec<SubclassView>,
+    /// Upcoming turns, soonest first, in game-time units (100 = one normal turn).
+    pub turn_order: Vec<TurnOrderEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnOrderEntry {
+    pub entity_id: u32,
+    pub name: String,
+    pub time_until: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -602,15 +858,31 @@ pub struct PlayerState {
     pub xp: u32,
     pub xp_to_next: u32,
     pub gold: u32,
+    /// Salvaged metal, spent on repairs at the anvil.
+    pub scrap: u32,
     pub inventory: Vec<ItemView>,
     pub equipment: EquipmentView,
+    /// Weight carried (after bags) against what the player can carry freely.
+    pub carry_weight: u32,
+    pub carry_capacity: u32,
+    pub encumbrance: Encumbrance,
+    /// Active equipment set bonuses, e.g. "Iron Bulwark (2/3): +2 defense".
+    pub set_bonuses: Vec<String>,
     pub status_effects: Vec<StatusView>,
     pub player_class: PlayerClass,
+    pub subclass: Option<Subclass>,
+    pub name: Option<String>,
+    pub background: Option<Background>,
+    /// Patron god and standing with them, once pledged at an altar.
+    pub deity: Option<DeityStatus>,
     pub mana: i32,
     pub max_mana: i32,
     pub abilities: Vec<AbilityView>,
     pub hunger: i32,
     pub max_hunger: i32,
+    pub hunger_state: HungerState,
+    /// Standing by an altar that will still take an offering.
+    pub at_altar: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -619,6 +891,15 @@ pub struct AbilityView {
     pub name: String,
     pub mana_cost: i32,
     pub description: String,
+    pub rank: u32,
+    pub cooldown: u32,
+    /// Turns until the next charge returns (0 when fully recharged).
+    pub cooldown_remaining: u32,
+    /// Charges left, for abilities that hold more than one.
+    pub charges: Option<u32>,
+    pub max_charges: Option<u32>,
+    /// Off cooldown with a charge to spend (mana is checked separately).
+    pub ready: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -652,6 +933,30 @@ pub struct ItemView {
     pub slot: Option<EquipSlot>,
     pub charges: Option<u32>,
     pub identified: bool,
+    /// True once the player knows this item is cursed.
+    #[serde(default)]
+    pub cursed: bool,
+    /// Affix descriptions, e.g. "Keen: +10% crit chance".
+    #[serde(default)]
+    pub affixes: Vec<String>,
+    pub quantity: u32,
+    /// Current and maximum durability; both 0 for items that don't wear.
+    #[serde(default)]
+    pub durability: u32,
+    #[serde(default)]
+    pub max_durability: u32,
+    /// What the artifact does, for unique artifacts.
+    #[serde(default)]
+    pub artifact: Option<String>,
+    /// Weight of the whole stack.
+    #[serde(default)]
+    pub weight: u32,
+    /// A raw corpse that can go over a fire.
+    #[serde(default)]
+    pub cookable: bool,
+    /// A known Scroll of Identify, read on a chosen pack item.
+    #[serde(default)]
+    pub identifies: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -678,6 +983,85 @@ pub struct LogMessage {
     pub severity: LogSeverity,
 }
 
+/// How weighed down an entity is by what it carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Encumbrance {
+    Unburdened,
+    Burdened,
+    Stressed,
+    Overloaded,
+}
+
+impl Encumbrance {
+    pub fn speed_penalty(&self) -> i32 {
+        match self {
+            Encumbrance::Unburdened => 0,
+            Encumbrance::Burdened => 10,
+            Encumbrance::Stressed => 25,
+            Encumbrance::Overloaded => 50,
+        }
+    }
+
+    pub fn dodge_penalty(&self) -> f32 {
+        match self {
+            Encumbrance::Unburdened => 0.0,
+            Encumbrance::Burdened => 0.05,
+            Encumbrance::Stressed => 0.15,
+            Encumbrance::Overloaded => 1.0,
+        }
+    }
+}
+
+/// How well fed the player is, by the hunger clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HungerState {
+    Satiated,
+    #[default]
+    NotHungry,
+    Hungry,
+    Weak,
+    Fainting,
+    Starving,
+}
+
+impl HungerState {
+    pub fn for_level(hunger: i32) -> HungerState {
+        match hunger {
+            i32::MIN..=0 => HungerState::Starving,
+            1..=100 => HungerState::Fainting,
+            101..=250 => HungerState::Weak,
+            251..=500 => HungerState::Hungry,
+            501..=750 => HungerState::NotHungry,
+            _ => HungerState::Satiated,
+        }
+    }
+
+    pub fn attack_penalty(&self) -> i32 {
+        match self {
+            HungerState::Satiated | HungerState::NotHungry => 0,
+            HungerState::Hungry => 1,
+            HungerState::Weak => 2,
+            HungerState::Fainting | HungerState::Starving => 3,
+        }
+    }
+
+    pub fn defense_penalty(&self) -> i32 {
+        match self {
+            HungerState::Satiated | HungerState::NotHungry | HungerState::Hungry => 0,
+            HungerState::Weak => 1,
+            HungerState::Fainting | HungerState::Starving => 2,
+        }
+    }
+
+    pub fn speed_penalty(&self) -> i32 {
+        match self {
+            HungerState::Satiated | HungerState::NotHungry | HungerState::Hungry => 0,
+            HungerState::Weak => 10,
+            HungerState::Fainting | HungerState::Starving => 20,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LogSeverity {
     Info,
@@ -786,6 +1170,9 @@ pub enum GameEvent {
     GoldGained {
         amount: u32,
     },
+    GoldLost {
+        amount: u32,
+    },
     BarrelSmashed {
         position: Position,
         dropped_item: Option<String>,
@@ -806,6 +1193,17 @@ pub enum GameEvent {
         item_name: String,
         stat_gained: String,
     },
+    PrayerAnswered {
+        deity: Deity,
+        /// True when the god answered in anger.
+        wrath: bool,
+        effect: String,
+    },
+    Rested {
+        turns: u32,
+        hp_restored: i32,
+        mana_restored: i32,
+    },
     AchievementUnlocked {
         name: String,
     },
@@ -827,6 +1225,25 @@ pub enum GameEvent {
         item_name: String,
         new_level: i32,
     },
+    ItemRepaired {
+        item_name: String,
+    },
+    ItemSalvaged {
+        item_name: String,
+        scrap: u32,
+    },
+    RecipeDiscovered {
+        recipe_name: String,
+    },
+    ItemCrafted {
+        recipe_name: String,
+    },
+    CurseRevealed {
+        item_name: String,
+    },
+    CurseRemoved {
+        item_name: String,
+    },
     BossSummon {
         boss_name: String,
         summoned: Vec<String>,
@@ -844,6 +1261,8 @@ pub struct GameOverInfo {
     pub cause_of_death: String,
     pub epitaph: Option<String>,
     pub final_score: u32,
+    /// Shards this run adds to the meta progression wallet.
+    pub shards_earned: u32,
     pub run_summary: RunSummary,
 }
 
@@ -860,7 +1279,12 @@ pub struct RunSummary {
     pub victory: bool,
     pub timestamp: String,
     pub class: String,
+    pub subclass: Option<String>,
+    /// Name the player gave their character, if any.
+    pub name: Option<String>,
     pub modifiers: Vec<String>,
+    /// Played without meta bonuses.
+    pub pure: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -872,6 +1296,9 @@ pub struct HighScore {
     pub timestamp: String,
     pub victory: bool,
     pub class: String,
+    pub subclass: Option<String>,
+    pub name: Option<String>,
+    pub pure: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -884,6 +1311,109 @@ pub struct EntityDetail {
     pub defense: Option<i32>,
     pub status_effects: Vec<StatusView>,
     pub flavor_text: Option<String>,
+    /// Affix descriptions when inspecting a magic item on the floor.
+    #[serde(default)]
+    pub affixes: Vec<String>,
+}
+
+/// Where an item being inspected lives.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ItemSource {
+    Inventory { index: usize },
+    Shop { shop_id: u32, index: usize },
+    Floor { entity_id: u32 },
+}
+
+/// Full stats of an item, for the inspection panel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemDetail {
+    pub name: String,
+    pub item_type: ItemType,
+    pub slot: Option<EquipSlot>,
+    pub identified: bool,
+    pub power: i32,
+    pub speed_mod: i32,
+    pub enchant_level: i32,
+    /// What using the item does, e.g. "Heals 20 HP".
+    pub effect: Option<String>,
+    pub charges: Option<u32>,
+    pub ranged: Option<RangedStats>,
+    pub quantity: u32,
+    pub weight: u32,
+    pub durability: u32,
+    pub max_durability: u32,
+    pub cursed: bool,
+    pub affixes: Vec<String>,
+    pub artifact: Option<String>,
+    /// Asking price, for shop items.
+    pub price: Option<u32>,
+    /// How equipping it would change the player's stats, for gear.
+    pub comparison: Option<ItemComparison>,
+}
+
+/// Change in effective stats from swapping an item into its slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemComparison {
+    /// What is in the slot now, if anything.
+    pub equipped_name: Option<String>,
+    pub attack_delta: i32,
+    pub defense_delta: i32,
+    pub speed_delta: i32,
+}
+
+/// Expected outcome of a single attack, computed without rolling any dice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttackForecast {
+    pub damage_type: String,
+    pub min_damage: i32,
+    pub max_damage: i32,
+    pub avg_damage: f32,
+    pub hit_chance: f32,
+    pub crit_chance: f32,
+    pub kill_chance: f32,
+    /// Expected number of attacks to kill from current HP; None if it can't.
+    pub hits_to_kill: Option<u32>,
+}
+
+/// What is being aimed when previewing a target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TargetSource {
+    Ability { ability_id: String },
+    Item { index: usize },
+}
+
+/// The tiles and creatures an ability or item would affect, for the
+/// targeting overlay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetPreview {
+    pub valid: bool,
+    /// Why the target can't be used, when it isn't valid.
+    pub reason: Option<String>,
+    /// Where the effect lands. Scrolls burst around the reader and wands
+    /// zap the nearest creature in view, wherever the cursor is.
+    pub center: Position,
+    pub tiles: Vec<Position>,
+    pub hits: Vec<PreviewHit>,
+    /// e.g. "Your Wolf is in the blast."
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewHit {
+    pub entity_id: u32,
+    pub name: String,
+    /// One of the player's allies.
+    pub friendly: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombatPreview {
+    pub attacker_id: u32,
+    pub target_id: u32,
+    pub ability_id: Option<String>,
+    pub attack: AttackForecast,
+    /// The target's retaliation against the attacker, if it can fight back.
+    pub counter: Option<AttackForecast>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
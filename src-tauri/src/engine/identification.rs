@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use super::affixes::affixed_name;
+use super::entity::*;
+use super::items::all_items;
+
+const POTION_APPEARANCES: &[&str] = &[
+    "murky", "fizzy", "bubbling", "cloudy", "smoky", "viscous",
+    "glowing", "amber", "crimson", "inky", "milky", "effervescent",
+];
+
+const SCROLL_LABELS: &[&str] = &[
+    "XUM", "ZOK FOO", "ELBIB", "THARR", "VENZAR", "KIRJE",
+    "ABRA KA", "YUM YUM", "NR 9", "JUYED", "PRATYA", "GARVEN",
+];
+
+const WAND_MATERIALS: &[&str] = &[
+    "oak", "iron", "bone", "crystal", "copper", "ebony", "silver", "glass",
+];
+
+const RING_GEMS: &[&str] = &[
+    "ruby", "opal", "jade", "onyx", "sapphire", "topaz", "garnet", "moonstone",
+];
+
+/// Salt so appearance shuffles don't mirror the world RNG stream.
+const APPEARANCE_SALT: u64 = 0x1D3A_11CE;
+
+/// Item type, its appearance pool, and how an appearance is phrased.
+type AppearanceGroup = (ItemType, &'static [&'static str], fn(&str) -> String);
+
+/// Per-run item identification: which unidentified appearance each magic
+/// item kind wears this run, and which kinds the player has learned.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdentificationState {
+    /// Item kind name -> appearance, e.g. "Potion of Speed" -> "murky potion".
+    appearances: HashMap<String, String>,
+    known: HashSet<String>,
+}
+
+impl IdentificationState {
+    /// Shuffle appearances deterministically from the run seed. Uses its own
+    /// RNG so the world's stream (and thus map generation) is unaffected.
+    pub fn for_seed(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed ^ APPEARANCE_SALT);
+        let mut appearances = HashMap::new();
+
+        let groups: [AppearanceGroup; 4] = [
+            (ItemType::Potion, POTION_APPEARANCES, |a| format!("{} potion", a)),
+            (ItemType::Scroll, SCROLL_LABELS, |a| format!("scroll labeled {}", a)),
+            (ItemType::Wand, WAND_MATERIALS, |a| format!("{} wand", a)),
+            (ItemType::Ring, RING_GEMS, |a| format!("{} ring", a)),
+        ];
+        for (item_type, pool, describe) in groups {
+            let mut pool = pool.to_vec();
+            pool.shuffle(&mut rng);
+            let kinds = all_items()
+                .into_iter()
+                .filter(|t| t.item_type == item_type && t.min_floor < 99);
+            for (template, appearance) in kinds.zip(pool) {
+                appearances.insert(template.name.to_string(), describe(appearance));
+            }
+        }
+
+        Self { appearances, known: HashSet::new() }
+    }
+
+    /// Whether the player knows what this item kind is. Kinds without an
+    /// appearance (weapons, armor, food, unlock rewards) are always known.
+    pub fn is_known(&self, name: &str) -> bool {
+        !self.appearances.contains_key(name) || self.known.contains(name)
+    }
+
+    /// Learn an item kind. Returns true if it was previously unknown.
+    pub fn identify(&mut self, name: &str) -> bool {
+        if self.is_known(name) {
+            return false;
+        }
+        self.known.insert(name.to_string())
+    }
+
+    pub fn appearance(&self, name: &str) -> Option<&str> {
+        self.appearances.get(name).map(|s| s.as_str())
+    }
+
+    /// Whether this particular item shows its true name and charges. Under
+    /// the Cursed modifier individual items are also hidden until used.
+    pub fn is_item_identified(&self, item: &Entity) -> bool {
+        self.is_known(&item.name) && item.item.as_ref().is_none_or(|p| p.identified)
+    }
+
+    /// Name the player sees for an item. Affixes only show once the item
+    /// is identified.
+    pub fn display_name(&self, item: &Entity) -> String {
+        if self.is_item_identified(item) {
+            let affixes = item.item.as_ref().map_or(&[][..], |p| &p.affixes[..]);
+            return affixed_name(&item.name, affixes);
+        }
+        if let Some(appearance) = self.appearance(&item.name) {
+            return appearance.to_string();
+        }
+        match item.item.as_ref().map(|p| p.item_type) {
+            Some(ItemType::Potion) => "Unknown Potion".to_string(),
+            Some(ItemType::Scroll) => "Unknown Scroll".to_string(),
+            Some(ItemType::Food) => "Unknown Food".to_string(),
+            _ => item.name.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appearances_are_stable_per_seed_and_unique() {
+        let a = IdentificationState::for_seed(7);
+        let b = IdentificationState::for_seed(7);
+        assert_eq!(a.appearance("Health Potion"), b.appearance("Health Potion"));
+
+        let mut seen = HashSet::new();
+        for appearance in a.appearances.values() {
+            assert!(seen.insert(appearance.clone()), "Duplicate appearance {}", appearance);
+        }
+        assert!(a.appearance("Scroll of Teleport").unwrap().starts_with("scroll labeled "));
+        assert!(a.appearance("Short Sword").is_none());
+        for template in all_items() {
+            let magic = matches!(template.item_type, ItemType::Potion | ItemType::Scroll | ItemType::Wand | ItemType::Ring);
+            if magic && template.min_floor < 99 {
+                assert!(a.appearance(template.name).is_some(), "{} has no appearance", template.name);
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_shuffle_appearances() {
+        let differs = (0..20u64).any(|seed| {
+            IdentificationState::for_seed(seed).appearance("Health Potion")
+                != IdentificationState::for_seed(seed + 100).appearance("Health Potion")
+        });
+        assert!(differs);
+    }
+
+    #[test]
+    fn identify_marks_kind_known_once() {
+        let mut ids = IdentificationState::for_seed(1);
+        assert!(!ids.is_known("Wand of Fire"));
+        assert!(ids.identify("Wand of Fire"));
+        assert!(!ids.identify("Wand of Fire"));
+        assert!(ids.is_known("Wand of Fire"));
+        // Mundane kinds are always known
+        assert!(!ids.identify("Short Sword"));
+    }
+
+    #[test]
+    fn old_saves_without_appearances_show_true_names() {
+        let ids = IdentificationState::default();
+        assert!(ids.is_known("Health Potion"));
+    }
+}
@@ -1,5 +1,7 @@
 use super::entity::*;
 use super::combat;
+use super::dungeon::placement::next_id;
+use super::items;
 
 /// Calculate effective stats for a player entity including equipment.
 pub fn calculate_effective_stats(entity: &Entity) -> EffectiveStats {
@@ -16,9 +18,35 @@ pub struct EffectiveStats {
     pub speed: i32,
 }
 
-/// Try to add an item to an entity's inventory. Returns false if full.
+/// Whether two items can share a stack: same kind of stackable item, and
+/// identified the same way so a Cursed run can't leak names through merging.
+pub fn can_stack(a: &Entity, b: &Entity) -> bool {
+    match (&a.item, &b.item) {
+        (Some(pa), Some(pb)) => {
+            a.name == b.name
+                && pa.item_type.is_stackable()
+                && pa.item_type == pb.item_type
+                && pa.identified == pb.identified
+        }
+        _ => false,
+    }
+}
+
+/// Units in an item's stack.
+pub fn quantity(item: &Entity) -> u32 {
+    item.item.as_ref().map_or(1, |p| p.quantity)
+}
+
+/// Try to add an item to an entity's inventory, merging it into a matching
+/// stack if there is one. Returns false if full.
 pub fn add_to_inventory(entity: &mut Entity, item: Entity) -> bool {
     if let Some(ref mut inv) = entity.inventory {
+        if let Some(stack) = inv.items.iter_mut().find(|i| can_stack(i, &item)) {
+            if let Some(ref mut props) = stack.item {
+                props.quantity += quantity(&item);
+            }
+            return true;
+        }
         if inv.is_full() {
             return false;
         }
@@ -29,6 +57,36 @@ pub fn add_to_inventory(entity: &mut Entity, item: Entity) -> bool {
     }
 }
 
+/// Whether `item` would fit, either in a free slot or on an existing stack.
+pub fn has_room_for(entity: &Entity, item: &Entity) -> bool {
+    entity.inventory.as_ref().is_some_and(|inv| {
+        !inv.is_full() || inv.items.iter().any(|i| can_stack(i, item))
+    })
+}
+
+/// Split up to `count` units off a stack. The whole item (same id) comes
+/// back if that empties the stack; otherwise the split gets a fresh id.
+pub fn take_from_stack(entity: &mut Entity, item_id: EntityId, count: u32) -> Option<Entity> {
+    let inv = entity.inventory.as_mut()?;
+    let idx = inv.items.iter().position(|i| i.id == item_id)?;
+    if count >= quantity(&inv.items[idx]) {
+        return Some(inv.items.remove(idx));
+    }
+    let stack = &mut inv.items[idx];
+    let mut split = stack.clone();
+    split.id = next_id();
+    if let (Some(ref mut from), Some(ref mut to)) = (&mut stack.item, &mut split.item) {
+        from.quantity -= count;
+        to.quantity = count;
+    }
+    Some(split)
+}
+
+/// Use up one unit of a stack, removing it when it runs out.
+pub fn consume_one(entity: &mut Entity, item_id: EntityId) -> bool {
+    take_from_stack(entity, item_id, 1).is_some()
+}
+
 /// Remove an item from inventory by ID. Returns the removed item if found.
 pub fn remove_from_inventory(entity: &mut Entity, item_id: EntityId) -> Option<Entity> {
     if let Some(ref mut inv) = entity.inventory {
@@ -96,6 +154,85 @@ pub fn find_item_by_name(entity: &Entity, name: &str) -> Option<EntityId> {
         .map(|i| i.id)
 }
 
+/// Ammo and crafting materials are light enough to weigh by the bundle.
+const LIGHT_BUNDLE: u32 = 10;
+
+/// Weight of one unit of an item. Armor and shields weigh more the more
+/// they protect; enchanting doesn't make them heavier.
+pub fn unit_weight(props: &ItemProperties) -> u32 {
+    let base_power = (props.power - props.enchant_level).max(0) as u32;
+    match props.item_type {
+        ItemType::Weapon => 4,
+        ItemType::Armor if props.slot == Some(EquipSlot::Body) => 4 + base_power * 2,
+        ItemType::Armor => 2 + base_power,
+        ItemType::Shield => 3 + base_power * 2,
+        ItemType::Food => 2,
+        ItemType::Potion | ItemType::Scroll | ItemType::Wand | ItemType::Bag => 1,
+        ItemType::Projectile | ItemType::Material => 1,
+        ItemType::Ring | ItemType::Amulet | ItemType::Key => 0,
+    }
+}
+
+/// Weight of a whole stack.
+pub fn item_weight(item: &Entity) -> u32 {
+    let props = match &item.item {
+        Some(p) => p,
+        None => return 0,
+    };
+    match props.item_type {
+        ItemType::Projectile | ItemType::Material => props.quantity.div_ceil(LIGHT_BUNDLE) * unit_weight(props),
+        _ => props.quantity * unit_weight(props),
+    }
+}
+
+/// Total weight carried, worn gear included, less whatever the entity's
+/// bags take off. Each kind of item is covered by the roomiest bag for it.
+pub fn carried_weight(entity: &Entity) -> u32 {
+    let inv = match &entity.inventory {
+        Some(inv) => inv,
+        None => return 0,
+    };
+    let total: u32 = inv.items.iter().map(item_weight).sum();
+    let mut relief = 0;
+    for kind in [ItemType::Projectile, ItemType::Potion, ItemType::Scroll] {
+        let room = inv
+            .items
+            .iter()
+            .filter(|i| items::bag_holds(&i.name) == Some(kind))
+            .filter_map(|i| i.item.as_ref())
+            .map(|p| p.power.max(0) as u32)
+            .max()
+            .unwrap_or(0);
+        let held: u32 = inv
+            .items
+            .iter()
+            .filter(|i| i.item.as_ref().is_some_and(|p| p.item_type == kind))
+            .map(item_weight)
+            .sum();
+        relief += held.min(room);
+    }
+    total - relief
+}
+
+/// Encumbrance tier from carried weight: burdened past capacity, stressed
+/// past one and a half times it, overloaded past double.
+pub fn encumbrance(entity: &Entity) -> Encumbrance {
+    let capacity = entity.inventory.as_ref().map_or(0, |inv| inv.carry_capacity);
+    if capacity == 0 {
+        return Encumbrance::Unburdened;
+    }
+    let weight = carried_weight(entity);
+    if weight > capacity * 2 {
+        Encumbrance::Overloaded
+    } else if weight * 2 > capacity * 3 {
+        Encumbrance::Stressed
+    } else if weight > capacity {
+        Encumbrance::Burdened
+    } else {
+        Encumbrance::Unburdened
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,6 +255,7 @@ mod tests {
                 dodge_chance: 0.0,
                 ranged: None,
                 on_hit: None,
+                resistances: Vec::new(),
             }),
             ai: None,
             inventory: Some(Inventory::new(20)),
@@ -164,6 +302,13 @@ mod tests {
                 hunger_restore: 0,
                 enchant_level: 0,
                 identified: true,
+                resistances: Vec::new(),
+                cursed: false,
+                curse_known: false,
+                affixes: Vec::new(),
+                quantity: 1,
+                durability: 0,
+                max_durability: 0,
             }),
             status_effects: Vec::new(),
             fov: None,
@@ -241,4 +386,79 @@ mod tests {
         assert_eq!(find_item_by_name(&player, "Dagger"), Some(11));
         assert_eq!(find_item_by_name(&player, "Axe"), None);
     }
+
+    fn make_arrows(id: EntityId, count: u32) -> Entity {
+        let mut arrows = make_weapon(id, "Arrow", 0);
+        if let Some(ref mut props) = arrows.item {
+            props.item_type = ItemType::Projectile;
+            props.slot = None;
+            props.quantity = count;
+        }
+        arrows
+    }
+
+    #[test]
+    fn stackables_merge_even_when_full() {
+        let mut player = make_player();
+        player.inventory.as_mut().unwrap().max_size = 1;
+
+        assert!(add_to_inventory(&mut player, make_arrows(10, 5)));
+        assert!(has_room_for(&player, &make_arrows(11, 3)));
+        assert!(add_to_inventory(&mut player, make_arrows(11, 3)));
+
+        let inv = player.inventory.as_ref().unwrap();
+        assert_eq!(inv.items.len(), 1);
+        assert_eq!(quantity(&inv.items[0]), 8);
+        // Weapons never stack
+        assert!(!add_to_inventory(&mut player, make_weapon(12, "Sword", 5)));
+    }
+
+    #[test]
+    fn take_from_stack_splits_and_empties() {
+        let mut player = make_player();
+        add_to_inventory(&mut player, make_arrows(10, 5));
+
+        let split = take_from_stack(&mut player, 10, 2).unwrap();
+        assert_ne!(split.id, 10);
+        assert_eq!(quantity(&split), 2);
+        assert_eq!(quantity(&player.inventory.as_ref().unwrap().items[0]), 3);
+
+        let rest = take_from_stack(&mut player, 10, 10).unwrap();
+        assert_eq!(rest.id, 10);
+        assert_eq!(quantity(&rest), 3);
+        assert!(player.inventory.as_ref().unwrap().items.is_empty());
+    }
+
+    fn make_item(id: EntityId, name: &str, item_type: ItemType, power: i32, count: u32) -> Entity {
+        let mut item = make_weapon(id, name, power);
+        if let Some(ref mut props) = item.item {
+            props.item_type = item_type;
+            props.slot = None;
+            props.quantity = count;
+        }
+        item
+    }
+
+    #[test]
+    fn heavy_loads_slow_and_bags_lighten_them() {
+        let mut player = make_player();
+        player.inventory.as_mut().unwrap().carry_capacity = 10;
+        add_to_inventory(&mut player, make_weapon(10, "Sword", 5));
+        add_to_inventory(&mut player, make_arrows(11, 25));
+        assert_eq!(carried_weight(&player), 4 + 3);
+        assert_eq!(encumbrance(&player), Encumbrance::Unburdened);
+
+        add_to_inventory(&mut player, make_weapon(12, "Axe", 7));
+        assert_eq!(encumbrance(&player), Encumbrance::Burdened);
+        assert_eq!(calculate_effective_stats(&player).speed, 90);
+
+        add_to_inventory(&mut player, make_item(13, "Quiver", ItemType::Bag, 2, 1));
+        assert_eq!(carried_weight(&player), 4 + 4 + 3 + 1 - 2);
+        assert_eq!(encumbrance(&player), Encumbrance::Unburdened);
+
+        for id in 14..18 {
+            add_to_inventory(&mut player, make_weapon(id, "Mace", 4));
+        }
+        assert_eq!(encumbrance(&player), Encumbrance::Overloaded);
+    }
 }
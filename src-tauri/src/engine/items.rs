@@ -17,6 +17,62 @@ pub struct ItemTemplate {
     pub hunger_restore: i32,
 }
 
+impl ItemTemplate {
+    /// Charges a freshly made item carries. Ammo templates use `charges` as
+    /// their bundle size, which becomes the stack quantity instead.
+    pub fn item_charges(&self) -> Option<u32> {
+        if self.item_type == ItemType::Projectile {
+            None
+        } else {
+            self.charges
+        }
+    }
+
+    /// Units in a freshly made stack: a bundle for ammo, otherwise one.
+    pub fn stack_quantity(&self) -> u32 {
+        if self.item_type == ItemType::Projectile {
+            self.charges.unwrap_or(1)
+        } else {
+            1
+        }
+    }
+
+    /// Wear a freshly made item can take before breaking. Only weapons and
+    /// armor wear out.
+    pub fn max_durability(&self) -> u32 {
+        match self.item_type {
+            ItemType::Weapon => 50,
+            ItemType::Armor | ItemType::Shield => 60,
+            _ => 0,
+        }
+    }
+
+    /// Item properties for a fresh, plain, identified copy of the template.
+    pub fn properties(&self) -> ItemProperties {
+        ItemProperties {
+            item_type: self.item_type,
+            slot: self.slot,
+            power: self.power,
+            speed_mod: self.speed_mod,
+            effect: self.effect.clone(),
+            charges: self.item_charges(),
+            energy_cost: self.energy_cost,
+            ammo_type: self.ammo_type,
+            ranged: self.ranged,
+            hunger_restore: self.hunger_restore,
+            enchant_level: 0,
+            identified: true,
+            resistances: item_resistances(self.name),
+            cursed: false,
+            curse_known: false,
+            affixes: Vec::new(),
+            quantity: self.stack_quantity(),
+            durability: self.max_durability(),
+            max_durability: self.max_durability(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Rarity {
     Common,
@@ -288,7 +344,7 @@ pub fn all_items() -> Vec<ItemTemplate> {
         ItemTemplate {
             name: "Scroll of Fireball", glyph: 0x3F, item_type: ItemType::Scroll,
             slot: None, power: 0, speed_mod: 0,
-            effect: Some(ItemEffect::DamageArea { damage: 20, radius: 3 }),
+            effect: Some(ItemEffect::DamageArea { damage: 20, radius: 3, damage_type: DamageType::Fire }),
             charges: None, energy_cost: 100,
             min_floor: 5, rarity: Rarity::Rare,
             ammo_type: None, ranged: None, hunger_restore: 0,
@@ -302,6 +358,20 @@ pub fn all_items() -> Vec<ItemTemplate> {
             ammo_type: None, ranged: None, hunger_restore: 0,
         },
 
+        ItemTemplate {
+            name: "Scroll of Identify", glyph: 0x3F, item_type: ItemType::Scroll,
+            slot: None, power: 0, speed_mod: 0,
+            effect: Some(ItemEffect::Identify), charges: None, energy_cost: 100,
+            min_floor: 1, rarity: Rarity::Common,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+        ItemTemplate {
+            name: "Scroll of Remove Curse", glyph: 0x3F, item_type: ItemType::Scroll,
+            slot: None, power: 0, speed_mod: 0,
+            effect: Some(ItemEffect::RemoveCurse), charges: None, energy_cost: 100,
+            min_floor: 2, rarity: Rarity::Uncommon,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
         ItemTemplate {
             name: "Scroll of Detection", glyph: 0x3F, item_type: ItemType::Scroll,
             slot: None, power: 0, speed_mod: 0,
@@ -341,6 +411,7 @@ pub fn all_items() -> Vec<ItemTemplate> {
             effect: Some(ItemEffect::RangedAttack {
                 damage: 8,
                 status: Some((StatusType::Burning, 3)),
+                damage_type: DamageType::Fire,
             }),
             charges: Some(8), energy_cost: 100,
             min_floor: 4, rarity: Rarity::Rare,
@@ -352,6 +423,7 @@ pub fn all_items() -> Vec<ItemTemplate> {
             effect: Some(ItemEffect::RangedAttack {
                 damage: 8,
                 status: Some((StatusType::Slowed, 3)),
+                damage_type: DamageType::Cold,
             }),
             charges: Some(8), energy_cost: 100,
             min_floor: 4, rarity: Rarity::Rare,
@@ -363,6 +435,7 @@ pub fn all_items() -> Vec<ItemTemplate> {
             effect: Some(ItemEffect::RangedAttack {
                 damage: 12,
                 status: None,
+                damage_type: DamageType::Lightning,
             }),
             charges: Some(5), energy_cost: 100,
             min_floor: 6, rarity: Rarity::VeryRare,
@@ -419,6 +492,52 @@ pub fn all_items() -> Vec<ItemTemplate> {
             ranged: Some(RangedStats { range: 4, damage_bonus: 3 }), hunger_restore: 0,
         },
 
+        // Crafting materials
+        ItemTemplate {
+            name: "Empty Flask", glyph: 0x21, item_type: ItemType::Material,
+            slot: None, power: 0, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 100,
+            min_floor: 1, rarity: Rarity::Uncommon,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+        ItemTemplate {
+            name: "Feather", glyph: 0x2F, item_type: ItemType::Material,
+            slot: None, power: 0, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 100,
+            min_floor: 1, rarity: Rarity::Uncommon,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+        ItemTemplate {
+            name: "Cave Herb", glyph: 0x22, item_type: ItemType::Material,
+            slot: None, power: 0, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 100,
+            min_floor: 4, rarity: Rarity::Common,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+
+        // Bags (power is the weight they carry for you)
+        ItemTemplate {
+            name: "Quiver", glyph: 0x28, item_type: ItemType::Bag,
+            slot: None, power: 6, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 50,
+            min_floor: 2, rarity: Rarity::Uncommon,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+        ItemTemplate {
+            name: "Potion Belt", glyph: 0x28, item_type: ItemType::Bag,
+            slot: None, power: 5, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 50,
+            min_floor: 2, rarity: Rarity::Uncommon,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+        ItemTemplate {
+            name: "Scroll Case", glyph: 0x28, item_type: ItemType::Bag,
+            slot: None, power: 4, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 50,
+            min_floor: 3, rarity: Rarity::Uncommon,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+
         // Keys
         ItemTemplate {
             name: "Iron Key", glyph: 0x7E, item_type: ItemType::Key,
@@ -435,6 +554,15 @@ pub fn all_items() -> Vec<ItemTemplate> {
             ammo_type: None, ranged: None, hunger_restore: 0,
         },
 
+        // Class starting gear (kept out of the random loot pool)
+        ItemTemplate {
+            name: "Staff", glyph: 0x2F, item_type: ItemType::Weapon,
+            slot: Some(EquipSlot::MainHand), power: 2, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 100,
+            min_floor: 99, rarity: Rarity::Common,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+
         // Unlockable reward items (not in random loot pool due to min_floor: 99)
         ItemTemplate {
             name: "Blessed Sword", glyph: 0x2F, item_type: ItemType::Weapon,
@@ -464,6 +592,29 @@ pub fn all_items() -> Vec<ItemTemplate> {
             min_floor: 99, rarity: Rarity::VeryRare,
             ammo_type: None, ranged: None, hunger_restore: 0,
         },
+
+        // Artifacts (placed at most once per run by artifacts::roll_floor_artifact)
+        ItemTemplate {
+            name: "Bloodthirst Blade", glyph: 0x2F, item_type: ItemType::Weapon,
+            slot: Some(EquipSlot::MainHand), power: 6, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 100,
+            min_floor: 99, rarity: Rarity::VeryRare,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+        ItemTemplate {
+            name: "Shadow Shroud", glyph: 0x5B, item_type: ItemType::Armor,
+            slot: Some(EquipSlot::Body), power: 2, speed_mod: 10,
+            effect: None, charges: None, energy_cost: 100,
+            min_floor: 99, rarity: Rarity::VeryRare,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
+        ItemTemplate {
+            name: "Aegis of Thorns", glyph: 0x29, item_type: ItemType::Shield,
+            slot: Some(EquipSlot::OffHand), power: 3, speed_mod: 0,
+            effect: None, charges: None, energy_cost: 100,
+            min_floor: 99, rarity: Rarity::VeryRare,
+            ammo_type: None, ranged: None, hunger_restore: 0,
+        },
     ]
 }
 
@@ -475,6 +626,70 @@ pub fn get_loot_pool(floor: u32) -> Vec<&'static str> {
         .collect()
 }
 
+/// What an item's use effect does, for item details.
+pub fn describe_effect(effect: &ItemEffect) -> String {
+    match effect {
+        ItemEffect::Heal(amount) => format!("Heals {} HP", amount),
+        ItemEffect::DamageArea { damage, radius, damage_type } => {
+            format!("Deals {} {} damage within {} tiles", damage, damage_type.name(), radius)
+        }
+        ItemEffect::ApplyStatus { effect, duration } => format!("Grants {:?} for {} turns", effect, duration),
+        ItemEffect::RevealMap => "Reveals the floor layout".to_string(),
+        ItemEffect::RevealSecrets => "Reveals hidden doors and traps".to_string(),
+        ItemEffect::Teleport => "Teleports you to a random spot".to_string(),
+        ItemEffect::CureStatus => "Cures harmful status effects".to_string(),
+        ItemEffect::Identify => "Identifies an unknown item".to_string(),
+        ItemEffect::RemoveCurse => "Lifts curses from your gear".to_string(),
+        ItemEffect::Corpse { monster, cooked } => {
+            let risky = !cooked && crate::engine::corpses::get_def(monster).is_some_and(|d| d.raw_risk > 0.0);
+            if risky {
+                format!("Remains of {}; eaten raw it may make you sick", with_article(monster))
+            } else {
+                format!("Remains of {}, safe to eat", with_article(monster))
+            }
+        }
+        ItemEffect::RangedAttack { damage, status, damage_type } => match status {
+            Some((effect, duration)) => format!(
+                "Fires a bolt for {} {} damage and {:?} for {} turns",
+                damage,
+                damage_type.name(),
+                effect,
+                duration
+            ),
+            None => format!("Fires a bolt for {} {} damage", damage, damage_type.name()),
+        },
+    }
+}
+
+/// `name` with "a" or "an" in front, by its first letter.
+pub fn with_article(name: &str) -> String {
+    let vowel = name.chars().next().is_some_and(|c| "aeiou".contains(c.to_ascii_lowercase()));
+    format!("{} {}", if vowel { "an" } else { "a" }, name)
+}
+
+/// The kind of item a bag carries.
+pub fn bag_holds(name: &str) -> Option<ItemType> {
+    match name {
+        "Quiver" => Some(ItemType::Projectile),
+        "Potion Belt" => Some(ItemType::Potion),
+        "Scroll Case" => Some(ItemType::Scroll),
+        _ => None,
+    }
+}
+
+/// Resistance table granted by wearing an item.
+pub fn item_resistances(name: &str) -> Vec<Resistance> {
+    match name {
+        "Amulet of Resistance" => vec![
+            Resistance::new(DamageType::Fire, 50),
+            Resistance::new(DamageType::Cold, 50),
+            Resistance::new(DamageType::Lightning, 50),
+        ],
+        "Abyss Cloak" => vec![Resistance::new(DamageType::Necrotic, 50)],
+        _ => Vec::new(),
+    }
+}
+
 pub fn find_template(name: &str) -> Option<ItemTemplate> {
     all_items().into_iter().find(|t| t.name == name)
 }
@@ -499,7 +714,8 @@ mod tests {
                     assert!(item.slot.is_some(), "{} is equipment but has no slot", item.name);
                 }
                 ItemType::Potion | ItemType::Scroll | ItemType::Food
-                | ItemType::Wand | ItemType::Key | ItemType::Projectile => {
+                | ItemType::Wand | ItemType::Key | ItemType::Projectile
+                | ItemType::Material | ItemType::Bag => {
                     assert!(item.slot.is_none(), "{} is consumable but has a slot", item.name);
                 }
             }
@@ -553,6 +769,23 @@ mod tests {
         assert!(Rarity::Rare.weight() > Rarity::VeryRare.weight());
     }
 
+    #[test]
+    fn resistance_items_exist() {
+        for name in ["Amulet of Resistance", "Abyss Cloak"] {
+            assert!(find_template(name).is_some(), "{} should be a real item", name);
+            assert!(!item_resistances(name).is_empty());
+        }
+        assert!(item_resistances("Dagger").is_empty());
+    }
+
+    #[test]
+    fn bags_hold_something() {
+        for item in all_items().iter().filter(|t| t.item_type == ItemType::Bag) {
+            assert!(bag_holds(item.name).is_some(), "{} holds nothing", item.name);
+            assert!(item.power > 0);
+        }
+    }
+
     #[test]
     fn unique_item_names() {
         let items = all_items();
@@ -21,57 +21,6 @@ pub fn check_level_up(xp: u32, level: u32) -> (bool, u32) {
     }
 }
 
-/// Apply a level-up choice to the player entity.
-pub fn apply_level_up_choice(entity: &mut Entity, choice: LevelUpChoice) {
-    match choice {
-        LevelUpChoice::MaxHp => {
-            if let Some(ref mut health) = entity.health {
-                health.max += 10;
-                health.current += 10;
-            }
-        }
-        LevelUpChoice::Attack => {
-            if let Some(ref mut combat) = entity.combat {
-                combat.base_attack += 2;
-            }
-        }
-        LevelUpChoice::Defense => {
-            if let Some(ref mut combat) = entity.combat {
-                combat.base_defense += 2;
-            }
-        }
-        LevelUpChoice::Speed => {
-            if let Some(ref mut combat) = entity.combat {
-                combat.base_speed += 15;
-            }
-        }
-        LevelUpChoice::Cleave => {
-            // Handled at World level (cleave_bonus)
-        }
-        LevelUpChoice::Fortify => {
-            if let Some(ref mut combat) = entity.combat {
-                combat.base_defense += 3;
-            }
-        }
-        LevelUpChoice::Backstab => {
-            if let Some(ref mut combat) = entity.combat {
-                combat.crit_chance += 0.05;
-            }
-        }
-        LevelUpChoice::Evasion => {
-            if let Some(ref mut combat) = entity.combat {
-                combat.dodge_chance += 0.05;
-            }
-        }
-        LevelUpChoice::SpellPower => {
-            // Handled at World level (spell_power_bonus)
-        }
-        LevelUpChoice::ManaRegen => {
-            // Handled at World level (mana_regen)
-        }
-    }
-}
-
 /// Calculate score for a run.
 pub fn calculate_score(
     floor: u32,
@@ -92,43 +41,6 @@ pub fn calculate_score(
 mod tests {
     use super::*;
 
-    fn make_player() -> Entity {
-        Entity {
-            id: 0,
-            name: "Player".to_string(),
-            position: Position::new(5, 5),
-            glyph: 0x40,
-            render_order: RenderOrder::Player,
-            blocks_movement: true,
-            blocks_fov: false,
-            health: Some(Health::new(50)),
-            combat: Some(CombatStats {
-                base_attack: 5,
-                base_defense: 2,
-                base_speed: 100,
-                crit_chance: 0.05,
-                dodge_chance: 0.0,
-                ranged: None,
-                on_hit: None,
-            }),
-            ai: None,
-            inventory: Some(Inventory::new(20)),
-            equipment: Some(EquipmentSlots::empty()),
-            item: None,
-            status_effects: Vec::new(),
-            fov: Some(FieldOfView::new(8)),
-            door: None,
-            trap: None,
-            stair: None,
-            loot_table: None,
-            flavor_text: None,
-            shop: None,
-            interactive: None,
-            elite: None,
-            resurrection_timer: None,
-        }
-    }
-
     #[test]
     fn xp_threshold_scales_with_level() {
         assert_eq!(xp_to_next_level(1), 150);
@@ -150,35 +62,6 @@ mod tests {
         assert_eq!(remaining, 100);
     }
 
-    #[test]
-    fn hp_level_up() {
-        let mut player = make_player();
-        apply_level_up_choice(&mut player, LevelUpChoice::MaxHp);
-        assert_eq!(player.health.as_ref().unwrap().max, 60);
-        assert_eq!(player.health.as_ref().unwrap().current, 60);
-    }
-
-    #[test]
-    fn attack_level_up() {
-        let mut player = make_player();
-        apply_level_up_choice(&mut player, LevelUpChoice::Attack);
-        assert_eq!(player.combat.as_ref().unwrap().base_attack, 7);
-    }
-
-    #[test]
-    fn defense_level_up() {
-        let mut player = make_player();
-        apply_level_up_choice(&mut player, LevelUpChoice::Defense);
-        assert_eq!(player.combat.as_ref().unwrap().base_defense, 4);
-    }
-
-    #[test]
-    fn speed_level_up() {
-        let mut player = make_player();
-        apply_level_up_choice(&mut player, LevelUpChoice::Speed);
-        assert_eq!(player.combat.as_ref().unwrap().base_speed, 115);
-    }
-
     #[test]
     fn score_calculation() {
         let score = calculate_score(5, 10, 1, 3, false);
@@ -0,0 +1,289 @@
+use serde::{Deserialize, Serialize};
+
+use super::entity::*;
+
+/// What one rank of a meta upgrade grants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaEffect {
+    /// Extra gold at the start of every run.
+    StartingGold(u32),
+    /// An extra Health Potion at the start of every run.
+    ExtraPotion,
+    /// Every shopkeeper stocks one more item.
+    ShopStock,
+    UnlockClass(PlayerClass),
+    UnlockModifier(RunModifier),
+}
+
+pub struct MetaUpgrade {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub max_rank: u32,
+    /// Shards for the first rank; each later rank costs this much more.
+    pub base_cost: u32,
+    pub effect: MetaEffect,
+}
+
+impl MetaUpgrade {
+    /// Shards needed to buy the rank after `rank`.
+    pub fn cost(&self, rank: u32) -> u32 {
+        self.base_cost * (rank + 1)
+    }
+}
+
+pub const META_UPGRADES: &[MetaUpgrade] = &[
+    MetaUpgrade { id: "starting_gold", name: "Inheritance", description: "Start each run with 25 more gold per rank.", max_rank: 4, base_cost: 15, effect: MetaEffect::StartingGold(25) },
+    MetaUpgrade { id: "extra_potion", name: "Field Kit", description: "Start each run with an extra Health Potion per rank.", max_rank: 2, base_cost: 25, effect: MetaEffect::ExtraPotion },
+    MetaUpgrade { id: "shop_stock", name: "Trade Routes", description: "Shopkeepers stock one more item per rank.", max_rank: 2, base_cost: 30, effect: MetaEffect::ShopStock },
+    MetaUpgrade { id: "unlock_ranger", name: "Ranger", description: "Unlock the Ranger class.", max_rank: 1, base_cost: 40, effect: MetaEffect::UnlockClass(PlayerClass::Ranger) },
+    MetaUpgrade { id: "unlock_cleric", name: "Cleric", description: "Unlock the Cleric class.", max_rank: 1, base_cost: 40, effect: MetaEffect::UnlockClass(PlayerClass::Cleric) },
+    MetaUpgrade { id: "unlock_necromancer", name: "Necromancer", description: "Unlock the Necromancer class.", max_rank: 1, base_cost: 60, effect: MetaEffect::UnlockClass(PlayerClass::Necromancer) },
+    MetaUpgrade { id: "unlock_famine", name: "Lean Times", description: "Unlock the Famine run modifier.", max_rank: 1, base_cost: 30, effect: MetaEffect::UnlockModifier(RunModifier::Famine) },
+];
+
+/// Shards a finished run earns.
+pub fn shards_for_run(floor: u32, bosses_killed: u32, score: u32) -> u32 {
+    floor * 2 + bosses_killed * 10 + score / 250
+}
+
+/// Starting bonuses bought with shards, applied to non-pure runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetaBonuses {
+    pub starting_gold: u32,
+    pub extra_potions: u32,
+    pub shop_stock: u32,
+}
+
+pub fn get_bonuses(conn: &rusqlite::Connection) -> MetaBonuses {
+    let mut bonuses = MetaBonuses::default();
+    for upgrade in META_UPGRADES {
+        let rank = get_rank(conn, upgrade.id);
+        match upgrade.effect {
+            MetaEffect::StartingGold(gold) => bonuses.starting_gold += gold * rank,
+            MetaEffect::ExtraPotion => bonuses.extra_potions += rank,
+            MetaEffect::ShopStock => bonuses.shop_stock += rank,
+            _ => {}
+        }
+    }
+    bonuses
+}
+
+/// Classes without an unlock upgrade are always available.
+pub fn is_class_unlocked(conn: &rusqlite::Connection, class: PlayerClass) -> bool {
+    META_UPGRADES
+        .iter()
+        .filter(|u| u.effect == MetaEffect::UnlockClass(class))
+        .all(|u| get_rank(conn, u.id) > 0)
+}
+
+/// Modifiers without an unlock upgrade are always available.
+pub fn is_modifier_unlocked(conn: &rusqlite::Connection, modifier: RunModifier) -> bool {
+    META_UPGRADES
+        .iter()
+        .filter(|u| u.effect == MetaEffect::UnlockModifier(modifier))
+        .all(|u| get_rank(conn, u.id) > 0)
+}
+
+pub fn buy_upgrade(conn: &rusqlite::Connection, id: &str) -> Result<(), String> {
+    let upgrade = META_UPGRADES.iter().find(|u| u.id == id).ok_or("Unknown upgrade.")?;
+    let rank = get_rank(conn, id);
+    if rank >= upgrade.max_rank {
+        return Err(format!("{} is already at its highest rank.", upgrade.name));
+    }
+    let cost = upgrade.cost(rank);
+    let shards = get_shards(conn);
+    if shards < cost {
+        return Err(format!("{} needs {} shards; you have {}.", upgrade.name, cost, shards));
+    }
+    let tx = conn.unchecked_transaction().map_err(|e| format!("Transaction error: {}", e))?;
+    set_shards(&tx, shards - cost)?;
+    set_rank(&tx, id, rank + 1)?;
+    tx.commit().map_err(|e| format!("Commit error: {}", e))
+}
+
+/// Take back every upgrade and refund the shards spent on them.
+pub fn reset_upgrades(conn: &rusqlite::Connection) -> Result<(), String> {
+    let refund: u32 = META_UPGRADES
+        .iter()
+        .map(|u| (0..get_rank(conn, u.id)).map(|r| u.cost(r)).sum::<u32>())
+        .sum();
+    let tx = conn.unchecked_transaction().map_err(|e| format!("Transaction error: {}", e))?;
+    set_shards(&tx, get_shards(&tx) + refund)?;
+    tx.execute("DELETE FROM meta_upgrades", [])
+        .map_err(|e| format!("Failed to reset upgrades: {}", e))?;
+    tx.commit().map_err(|e| format!("Commit error: {}", e))
+}
+
+pub fn award_shards(conn: &rusqlite::Connection, amount: u32) -> Result<(), String> {
+    set_shards(conn, get_shards(conn) + amount)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaUpgradeView {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub rank: u32,
+    pub max_rank: u32,
+    /// None once the upgrade is maxed.
+    pub next_cost: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaStatus {
+    pub shards: u32,
+    pub upgrades: Vec<MetaUpgradeView>,
+    pub locked_classes: Vec<PlayerClass>,
+    pub locked_modifiers: Vec<RunModifier>,
+}
+
+pub fn get_status(conn: &rusqlite::Connection) -> MetaStatus {
+    let upgrades = META_UPGRADES
+        .iter()
+        .map(|u| {
+            let rank = get_rank(conn, u.id);
+            MetaUpgradeView {
+                id: u.id.to_string(),
+                name: u.name.to_string(),
+                description: u.description.to_string(),
+                rank,
+                max_rank: u.max_rank,
+                next_cost: (rank < u.max_rank).then(|| u.cost(rank)),
+            }
+        })
+        .collect();
+    let locked = || META_UPGRADES.iter().filter(|u| get_rank(conn, u.id) == 0);
+    let locked_classes = locked()
+        .filter_map(|u| match u.effect {
+            MetaEffect::UnlockClass(class) => Some(class),
+            _ => None,
+        })
+        .collect();
+    let locked_modifiers = locked()
+        .filter_map(|u| match u.effect {
+            MetaEffect::UnlockModifier(modifier) => Some(modifier),
+            _ => None,
+        })
+        .collect();
+    MetaStatus { shards: get_shards(conn), upgrades, locked_classes, locked_modifiers }
+}
+
+// --- DB helpers ---
+
+pub fn ensure_table(conn: &rusqlite::Connection) {
+    let _ = conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS meta_upgrades (
+            id TEXT PRIMARY KEY,
+            rank INTEGER NOT NULL DEFAULT 0
+        );
+        CREATE TABLE IF NOT EXISTS meta_wallet (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            shards INTEGER NOT NULL DEFAULT 0
+        );"
+    );
+}
+
+fn get_rank(conn: &rusqlite::Connection, id: &str) -> u32 {
+    conn.query_row(
+        "SELECT rank FROM meta_upgrades WHERE id = ?1",
+        [id],
+        |row| row.get::<_, u32>(0),
+    )
+    .unwrap_or(0)
+}
+
+fn set_rank(conn: &rusqlite::Connection, id: &str, rank: u32) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO meta_upgrades (id, rank) VALUES (?1, ?2)
+         ON CONFLICT(id) DO UPDATE SET rank = ?2",
+        rusqlite::params![id, rank],
+    )
+    .map_err(|e| format!("Failed to save upgrade: {}", e))?;
+    Ok(())
+}
+
+fn get_shards(conn: &rusqlite::Connection) -> u32 {
+    conn.query_row("SELECT shards FROM meta_wallet WHERE id = 1", [], |row| row.get::<_, u32>(0))
+        .unwrap_or(0)
+}
+
+fn set_shards(conn: &rusqlite::Connection, shards: u32) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO meta_wallet (id, shards) VALUES (1, ?1)
+         ON CONFLICT(id) DO UPDATE SET shards = ?1",
+        [shards],
+    )
+    .map_err(|e| format!("Failed to save shards: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        ensure_table(&conn);
+        conn
+    }
+
+    #[test]
+    fn upgrades_cost_shards_and_stack() {
+        let conn = test_db();
+        assert!(buy_upgrade(&conn, "starting_gold").is_err());
+
+        award_shards(&conn, 50).unwrap();
+        buy_upgrade(&conn, "starting_gold").unwrap();
+        buy_upgrade(&conn, "starting_gold").unwrap();
+        assert_eq!(get_shards(&conn), 5);
+        assert_eq!(get_bonuses(&conn).starting_gold, 50);
+        assert!(buy_upgrade(&conn, "starting_gold").is_err());
+    }
+
+    #[test]
+    fn unlocks_gate_new_classes() {
+        let conn = test_db();
+        assert!(is_class_unlocked(&conn, PlayerClass::Warrior));
+        assert!(!is_class_unlocked(&conn, PlayerClass::Necromancer));
+
+        award_shards(&conn, 60).unwrap();
+        buy_upgrade(&conn, "unlock_necromancer").unwrap();
+        assert!(is_class_unlocked(&conn, PlayerClass::Necromancer));
+        assert!(!get_status(&conn).locked_classes.contains(&PlayerClass::Necromancer));
+    }
+
+    #[test]
+    fn unlocks_gate_only_new_modifiers() {
+        let conn = test_db();
+        assert!(is_modifier_unlocked(&conn, RunModifier::Pacifist));
+        assert!(!is_modifier_unlocked(&conn, RunModifier::Famine));
+        assert_eq!(get_status(&conn).locked_modifiers, vec![RunModifier::Famine]);
+
+        award_shards(&conn, 30).unwrap();
+        buy_upgrade(&conn, "unlock_famine").unwrap();
+        assert!(is_modifier_unlocked(&conn, RunModifier::Famine));
+        assert!(get_status(&conn).locked_modifiers.is_empty());
+    }
+
+    #[test]
+    fn reset_refunds_everything() {
+        let conn = test_db();
+        award_shards(&conn, 150).unwrap();
+        buy_upgrade(&conn, "extra_potion").unwrap();
+        buy_upgrade(&conn, "extra_potion").unwrap();
+        buy_upgrade(&conn, "unlock_cleric").unwrap();
+        assert_eq!(get_shards(&conn), 150 - 25 - 50 - 40);
+
+        reset_upgrades(&conn).unwrap();
+        assert_eq!(get_shards(&conn), 150);
+        assert_eq!(get_bonuses(&conn), MetaBonuses::default());
+    }
+
+    #[test]
+    fn runs_earn_shards() {
+        assert_eq!(shards_for_run(1, 0, 150), 2);
+        assert_eq!(shards_for_run(10, 2, 8000), 20 + 20 + 32);
+    }
+}
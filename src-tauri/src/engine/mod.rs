@@ -12,5 +12,18 @@ pub mod items;
 pub mod enemies;
 pub mod level;
 pub mod achievements;
+pub mod meta;
 pub mod classes;
 pub mod abilities;
+pub mod bosses;
+pub mod scheduler;
+pub mod identification;
+pub mod affixes;
+pub mod recipes;
+pub mod artifacts;
+pub mod sets;
+pub mod talents;
+pub mod subclasses;
+pub mod deities;
+pub mod backgrounds;
+pub mod corpses;
@@ -185,7 +185,17 @@ fn chebyshev(a: Position, b: Position) -> i32 {
 }
 
 pub fn has_line_of_sight(map: &Map, from: Position, to: Position) -> bool {
-    // Bresenham's line algorithm
+    // Every tile between the endpoints must be see-through
+    bresenham_line(from, to)
+        .into_iter()
+        .take_while(|p| *p != to)
+        .all(|p| !map.is_opaque(p.x, p.y))
+}
+
+/// Tiles on the Bresenham line from `from` to `to`, excluding the origin
+/// and including the destination.
+pub fn bresenham_line(from: Position, to: Position) -> Vec<Position> {
+    let mut line = Vec::new();
     let mut x = from.x;
     let mut y = from.y;
     let dx = (to.x - from.x).abs();
@@ -194,14 +204,7 @@ pub fn has_line_of_sight(map: &Map, from: Position, to: Position) -> bool {
     let sy = if from.y < to.y { 1 } else { -1 };
     let mut err = dx + dy;
 
-    loop {
-        if x == to.x && y == to.y {
-            return true;
-        }
-        // Check if this tile blocks sight (skip origin)
-        if (x != from.x || y != from.y) && map.is_opaque(x, y) {
-            return false;
-        }
+    while x != to.x || y != to.y {
         let e2 = 2 * err;
         if e2 >= dy {
             err += dy;
@@ -211,7 +214,9 @@ pub fn has_line_of_sight(map: &Map, from: Position, to: Position) -> bool {
             err += dx;
             y += sy;
         }
+        line.push(Position::new(x, y));
     }
+    line
 }
 
 #[cfg(test)]
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a recipe can be made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CraftStation {
+    Anywhere,
+    Anvil,
+    Altar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeOutput {
+    /// A fresh item (a full bundle for ammo) added to the pack.
+    Item(&'static str),
+    /// Toughen a piece of armor or a shield the player picks: +1 power, up
+    /// to the same +3 cap as weapon enchanting.
+    ReinforceArmor,
+}
+
+pub struct Recipe {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub station: CraftStation,
+    /// Pack items used up, by name and count.
+    pub ingredients: &'static [(&'static str, u32)],
+    /// Scrap (from salvaged gear) used up.
+    pub scrap: u32,
+    pub output: RecipeOutput,
+}
+
+impl Recipe {
+    pub fn needs_target(&self) -> bool {
+        self.output == RecipeOutput::ReinforceArmor
+    }
+
+    /// Human-readable cost, e.g. ["2x Cave Herb", "Empty Flask", "1 scrap"].
+    pub fn describe_ingredients(&self) -> Vec<String> {
+        let mut parts: Vec<String> = self
+            .ingredients
+            .iter()
+            .map(|(name, count)| if *count > 1 { format!("{}x {}", count, name) } else { name.to_string() })
+            .collect();
+        if self.needs_target() {
+            parts.push("armor or shield".to_string());
+        }
+        if self.scrap > 0 {
+            parts.push(format!("{} scrap", self.scrap));
+        }
+        parts
+    }
+
+    /// Whether the pack (as counted by `count_of`) and scrap cover the cost.
+    pub fn has_ingredients(&self, count_of: impl Fn(&str) -> u32, scrap: u32) -> bool {
+        scrap >= self.scrap && self.ingredients.iter().all(|(name, count)| count_of(name) >= *count)
+    }
+}
+
+pub fn all_recipes() -> Vec<Recipe> {
+    vec![
+        Recipe {
+            id: "brew_health_potion", name: "Brew Health Potion", station: CraftStation::Anywhere,
+            ingredients: &[("Cave Herb", 2), ("Empty Flask", 1)], scrap: 0,
+            output: RecipeOutput::Item("Health Potion"),
+        },
+        Recipe {
+            id: "brew_antidote", name: "Brew Antidote", station: CraftStation::Anywhere,
+            ingredients: &[("Cave Herb", 1), ("Empty Flask", 1)], scrap: 0,
+            output: RecipeOutput::Item("Antidote"),
+        },
+        Recipe {
+            id: "bless_greater_health_potion", name: "Bless Greater Health Potion", station: CraftStation::Altar,
+            ingredients: &[("Health Potion", 1), ("Cave Herb", 3)], scrap: 0,
+            output: RecipeOutput::Item("Greater Health Potion"),
+        },
+        Recipe {
+            id: "fletch_arrows", name: "Fletch Arrows", station: CraftStation::Anywhere,
+            ingredients: &[("Feather", 2)], scrap: 1,
+            output: RecipeOutput::Item("Arrow"),
+        },
+        Recipe {
+            id: "forge_bolts", name: "Forge Bolts", station: CraftStation::Anvil,
+            ingredients: &[("Feather", 1)], scrap: 2,
+            output: RecipeOutput::Item("Bolt"),
+        },
+        Recipe {
+            id: "forge_throwing_knives", name: "Forge Throwing Knives", station: CraftStation::Anvil,
+            ingredients: &[], scrap: 4,
+            output: RecipeOutput::Item("Throwing Knife"),
+        },
+        Recipe {
+            id: "reinforce_armor", name: "Reinforce Armor", station: CraftStation::Anvil,
+            ingredients: &[], scrap: 3,
+            output: RecipeOutput::ReinforceArmor,
+        },
+    ]
+}
+
+pub fn find_recipe(id: &str) -> Option<Recipe> {
+    all_recipes().into_iter().find(|r| r.id == id)
+}
+
+/// A known recipe as shown in the recipe book.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeView {
+    pub id: String,
+    pub name: String,
+    pub station: CraftStation,
+    pub ingredients: Vec<String>,
+    pub needs_target: bool,
+    /// The pack holds everything the recipe uses up.
+    pub has_ingredients: bool,
+    /// ...and the player is at the right station to make it now.
+    pub craftable: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::items::find_template;
+
+    #[test]
+    fn recipes_reference_real_items() {
+        for recipe in all_recipes() {
+            for (name, count) in recipe.ingredients {
+                assert!(find_template(name).is_some(), "{} uses unknown item {}", recipe.id, name);
+                assert!(*count > 0);
+            }
+            if let RecipeOutput::Item(name) = recipe.output {
+                assert!(find_template(name).is_some(), "{} makes unknown item {}", recipe.id, name);
+            }
+        }
+    }
+
+    #[test]
+    fn ingredient_check_counts_stacks_and_scrap() {
+        let recipe = find_recipe("fletch_arrows").unwrap();
+        let count_of = |name: &str| if name == "Feather" { 2 } else { 0 };
+        assert!(recipe.has_ingredients(count_of, 1));
+        assert!(!recipe.has_ingredients(count_of, 0));
+        assert!(!recipe.has_ingredients(|_| 1, 5));
+        assert_eq!(recipe.describe_ingredients(), vec!["2x Feather", "1 scrap"]);
+    }
+}
@@ -0,0 +1,193 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use serde::{Deserialize, Serialize};
+
+use super::entity::EntityId;
+
+/// Game time in one full turn at normal (100) speed. Once-per-turn upkeep
+/// (status effects, hunger, mana) runs each time the clock crosses a multiple.
+pub const TURN_LENGTH: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionKind {
+    Move,
+    Attack,
+    RangedAttack,
+    Quaff,
+    Eat,
+    Read,
+    Zap,
+    Equip,
+    PickUp,
+    Drop,
+    Cast,
+    Interact,
+    Wait,
+}
+
+impl ActionKind {
+    /// Time the action takes at speed 100.
+    pub fn base_cost(&self) -> u64 {
+        match self {
+            ActionKind::Move => 100,
+            ActionKind::Attack => 100,
+            ActionKind::RangedAttack => 110,
+            ActionKind::Quaff => 80,
+            ActionKind::Eat => 150,
+            ActionKind::Read => 110,
+            ActionKind::Zap => 90,
+            ActionKind::Equip => 120,
+            ActionKind::PickUp => 80,
+            ActionKind::Drop => 50,
+            ActionKind::Cast => 120,
+            ActionKind::Interact => 100,
+            ActionKind::Wait => 100,
+        }
+    }
+}
+
+/// Time an action of `base_cost` takes for an actor at `speed`.
+/// Hasted (+30 speed) acts in 100/130 of the time, Slowed (-30) in 100/70.
+pub fn action_time(base_cost: u64, speed: i32) -> u64 {
+    (base_cost * 100 / speed.max(1) as u64).max(1)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct ScheduledTurn {
+    ready_at: u64,
+    /// Insertion order, so actors ready at the same time act first-come first-served.
+    seq: u64,
+    entity_id: EntityId,
+}
+
+impl Ord for ScheduledTurn {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed: BinaryHeap is a max-heap and we want the earliest turn on top
+        other
+            .ready_at
+            .cmp(&self.ready_at)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for ScheduledTurn {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Priority queue of monster turns keyed on game time. The player is not
+/// queued: their turn is whenever input arrives, and `World::resolve_turn`
+/// advances the clock by the cost of what they did.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scheduler {
+    now: u64,
+    next_seq: u64,
+    queue: BinaryHeap<ScheduledTurn>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Move the clock forward. Never moves backwards.
+    pub fn advance_to(&mut self, time: u64) {
+        self.now = self.now.max(time);
+    }
+
+    /// Queue an entity's next turn `delay` after now, replacing any turn
+    /// it already had queued.
+    pub fn schedule(&mut self, entity_id: EntityId, delay: u64) {
+        self.remove(entity_id);
+        self.queue.push(ScheduledTurn {
+            ready_at: self.now + delay,
+            seq: self.next_seq,
+            entity_id,
+        });
+        self.next_seq += 1;
+    }
+
+    pub fn contains(&self, entity_id: EntityId) -> bool {
+        self.queue.iter().any(|t| t.entity_id == entity_id)
+    }
+
+    pub fn remove(&mut self, entity_id: EntityId) {
+        self.queue.retain(|t| t.entity_id != entity_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Pop the earliest turn if it is due at or before `until`, moving the
+    /// clock to that turn.
+    pub fn pop_due(&mut self, until: u64) -> Option<EntityId> {
+        if self.queue.peek()?.ready_at > until {
+            return None;
+        }
+        let turn = self.queue.pop()?;
+        self.advance_to(turn.ready_at);
+        Some(turn.entity_id)
+    }
+
+    /// Time until the entity's queued turn, if it has one.
+    pub fn time_until(&self, entity_id: EntityId) -> Option<u64> {
+        self.queue
+            .iter()
+            .find(|t| t.entity_id == entity_id)
+            .map(|t| t.ready_at.saturating_sub(self.now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pops_in_time_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(1, 100);
+        scheduler.schedule(2, 50);
+        scheduler.schedule(3, 100);
+
+        assert_eq!(scheduler.pop_due(200), Some(2));
+        assert_eq!(scheduler.now(), 50);
+        // Ties resolve in scheduling order
+        assert_eq!(scheduler.pop_due(200), Some(1));
+        assert_eq!(scheduler.pop_due(200), Some(3));
+        assert_eq!(scheduler.pop_due(200), None);
+    }
+
+    #[test]
+    fn pop_due_respects_horizon() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(1, 150);
+        assert_eq!(scheduler.pop_due(100), None);
+        assert_eq!(scheduler.now(), 0);
+        assert_eq!(scheduler.time_until(1), Some(150));
+    }
+
+    #[test]
+    fn rescheduling_replaces_existing_turn() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(1, 100);
+        scheduler.schedule(1, 30);
+        assert_eq!(scheduler.time_until(1), Some(30));
+        assert_eq!(scheduler.pop_due(1000), Some(1));
+        assert!(!scheduler.contains(1));
+    }
+
+    #[test]
+    fn speed_scales_action_time() {
+        assert_eq!(action_time(100, 100), 100);
+        assert_eq!(action_time(100, 130), 76);
+        assert_eq!(action_time(100, 70), 142);
+        assert!(action_time(ActionKind::Drop.base_cost(), 200) >= 1);
+    }
+}
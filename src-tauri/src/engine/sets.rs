@@ -0,0 +1,133 @@
+use super::entity::*;
+
+/// Bonuses granted once enough pieces of a set are worn together.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SetBonus {
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: i32,
+}
+
+pub struct ItemSet {
+    pub name: &'static str,
+    pub pieces: &'static [&'static str],
+    /// (pieces worn, bonus) in ascending order; only the highest reached
+    /// tier applies.
+    pub tiers: &'static [(usize, SetBonus)],
+}
+
+pub fn all_sets() -> Vec<ItemSet> {
+    vec![
+        ItemSet {
+            name: "Iron Bulwark",
+            pieces: &["Iron Helm", "Plate Armor", "Tower Shield"],
+            tiers: &[
+                (2, SetBonus { attack: 0, defense: 2, speed: 0 }),
+                (3, SetBonus { attack: 0, defense: 4, speed: 15 }),
+            ],
+        },
+        ItemSet {
+            name: "Wanderer's Garb",
+            pieces: &["Leather Cap", "Leather Armor", "Wooden Shield"],
+            tiers: &[
+                (2, SetBonus { attack: 0, defense: 0, speed: 10 }),
+                (3, SetBonus { attack: 0, defense: 2, speed: 10 }),
+            ],
+        },
+        ItemSet {
+            name: "Chainwarden",
+            pieces: &["Iron Helm", "Chain Mail", "Iron Shield"],
+            tiers: &[
+                (2, SetBonus { attack: 0, defense: 1, speed: 0 }),
+                (3, SetBonus { attack: 2, defense: 2, speed: 0 }),
+            ],
+        },
+    ]
+}
+
+/// Names of everything the entity has equipped and unbroken.
+fn worn_names(entity: &Entity) -> Vec<&str> {
+    let (equipment, inventory) = match (&entity.equipment, &entity.inventory) {
+        (Some(e), Some(inv)) => (e, inv),
+        _ => return Vec::new(),
+    };
+    [equipment.main_hand, equipment.off_hand, equipment.head, equipment.body, equipment.ring, equipment.amulet]
+        .iter()
+        .flatten()
+        .filter_map(|id| inventory.items.iter().find(|i| i.id == *id))
+        .filter(|item| item.item.as_ref().is_some_and(|p| !p.is_broken()))
+        .map(|item| item.name.as_str())
+        .collect()
+}
+
+/// Each set with at least its first tier active, with pieces worn and the
+/// bonus that applies. Affixes and enchants only decorate an item's base
+/// name, so a piece counts if its name contains the set piece's name.
+fn active_sets(entity: &Entity) -> Vec<(ItemSet, usize, SetBonus)> {
+    let worn = worn_names(entity);
+    all_sets()
+        .into_iter()
+        .filter_map(|set| {
+            let count = set.pieces.iter().filter(|piece| worn.iter().any(|name| name.contains(*piece))).count();
+            let bonus = set.tiers.iter().rev().find(|(needed, _)| count >= *needed)?.1;
+            Some((set, count, bonus))
+        })
+        .collect()
+}
+
+pub fn total_bonus(entity: &Entity) -> SetBonus {
+    active_sets(entity).iter().fold(SetBonus::default(), |acc, (_, _, b)| SetBonus {
+        attack: acc.attack + b.attack,
+        defense: acc.defense + b.defense,
+        speed: acc.speed + b.speed,
+    })
+}
+
+pub fn attack_bonus(entity: &Entity) -> i32 {
+    total_bonus(entity).attack
+}
+
+pub fn defense_bonus(entity: &Entity) -> i32 {
+    total_bonus(entity).defense
+}
+
+pub fn speed_bonus(entity: &Entity) -> i32 {
+    total_bonus(entity).speed
+}
+
+/// "Iron Bulwark (2/3): +2 defense" for each active set, for the HUD.
+pub fn active_set_descriptions(entity: &Entity) -> Vec<String> {
+    active_sets(entity)
+        .iter()
+        .map(|(set, count, bonus)| {
+            let mut parts = Vec::new();
+            if bonus.attack != 0 {
+                parts.push(format!("+{} attack", bonus.attack));
+            }
+            if bonus.defense != 0 {
+                parts.push(format!("+{} defense", bonus.defense));
+            }
+            if bonus.speed != 0 {
+                parts.push(format!("+{} speed", bonus.speed));
+            }
+            format!("{} ({}/{}): {}", set.name, count, set.pieces.len(), parts.join(", "))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::items::find_template;
+
+    #[test]
+    fn set_pieces_are_real_equipment() {
+        for set in all_sets() {
+            for piece in set.pieces {
+                let template = find_template(piece).unwrap_or_else(|| panic!("{} has unknown piece {}", set.name, piece));
+                assert!(template.slot.is_some(), "{} is not equippable", piece);
+            }
+            assert!(set.tiers.windows(2).all(|w| w[0].0 < w[1].0));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+use super::entity::*;
+
+/// Character level at which the specialization choice opens.
+pub const SUBCLASS_LEVEL: u32 = 5;
+
+pub struct SubclassDef {
+    pub subclass: Subclass,
+    pub class: PlayerClass,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Starting ability swapped out for the signature ability, if any.
+    pub replaces: Option<&'static str>,
+    /// Talent granted at rank 1 on choosing this specialization.
+    pub passive: &'static str,
+}
+
+pub fn all_subclasses() -> Vec<SubclassDef> {
+    vec![
+        SubclassDef {
+            subclass: Subclass::Berserker, class: PlayerClass::Warrior, name: "Berserker",
+            description: "Trade Shield Bash for Reckless Swing and hit harder when wounded.",
+            replaces: Some("shield_bash"), passive: "frenzy",
+        },
+        SubclassDef {
+            subclass: Subclass::Guardian, class: PlayerClass::Warrior, name: "Guardian",
+            description: "Gain Bulwark and extra defense.",
+            replaces: None, passive: "stalwart",
+        },
+        SubclassDef {
+            subclass: Subclass::Assassin, class: PlayerClass::Rogue, name: "Assassin",
+            description: "Trade Smoke Bomb for Assassinate and land more critical hits.",
+            replaces: Some("smoke_bomb"), passive: "killer_instinct",
+        },
+        SubclassDef {
+            subclass: Subclass::Trickster, class: PlayerClass::Rogue, name: "Trickster",
+            description: "Trade Dash for Shadow Step and dodge more often.",
+            replaces: Some("dash"), passive: "misdirection",
+        },
+        SubclassDef {
+            subclass: Subclass::Pyromancer, class: PlayerClass::Mage, name: "Pyromancer",
+            description: "Trade Frost Bolt for Immolate and burn hotter.",
+            replaces: Some("frost_bolt"), passive: "kindling",
+        },
+        SubclassDef {
+            subclass: Subclass::Cryomancer, class: PlayerClass::Mage, name: "Cryomancer",
+            description: "Trade Fireball for Frost Nova and hold foes longer.",
+            replaces: Some("fireball"), passive: "deep_chill",
+        },
+        SubclassDef {
+            subclass: Subclass::Beastmaster, class: PlayerClass::Ranger, name: "Beastmaster",
+            description: "Gain Call the Pack and keep summoned beasts longer.",
+            replaces: None, passive: "pack_bond",
+        },
+        SubclassDef {
+            subclass: Subclass::Sharpshooter, class: PlayerClass::Ranger, name: "Sharpshooter",
+            description: "Trade Snare for Piercing Shot and land more critical hits.",
+            replaces: Some("snare"), passive: "steady_aim",
+        },
+        SubclassDef {
+            subclass: Subclass::Crusader, class: PlayerClass::Cleric, name: "Crusader",
+            description: "Trade Smite for Holy Strike and gain attack.",
+            replaces: Some("smite"), passive: "zeal",
+        },
+        SubclassDef {
+            subclass: Subclass::Oracle, class: PlayerClass::Cleric, name: "Oracle",
+            description: "Trade Heal for Greater Heal and regain mana faster.",
+            replaces: Some("heal"), passive: "foresight",
+        },
+        SubclassDef {
+            subclass: Subclass::Lich, class: PlayerClass::Necromancer, name: "Lich",
+            description: "Gain Soul Rend and stronger spells.",
+            replaces: None, passive: "phylactery",
+        },
+        SubclassDef {
+            subclass: Subclass::Bonelord, class: PlayerClass::Necromancer, name: "Bonelord",
+            description: "Gain Bone Storm and keep raised dead longer.",
+            replaces: None, passive: "ossuary",
+        },
+    ]
+}
+
+pub fn get_def(subclass: Subclass) -> SubclassDef {
+    all_subclasses()
+        .into_iter()
+        .find(|d| d.subclass == subclass)
+        .expect("every subclass has a definition")
+}
+
+/// The specializations open to `class`.
+pub fn options(class: PlayerClass) -> Vec<SubclassDef> {
+    all_subclasses().into_iter().filter(|d| d.class == class).collect()
+}
+
+/// One specialization as offered at the milestone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubclassView {
+    pub subclass: Subclass,
+    pub name: String,
+    pub description: String,
+}
+
+pub fn option_views(class: PlayerClass) -> Vec<SubclassView> {
+    options(class)
+        .into_iter()
+        .map(|d| SubclassView {
+            subclass: d.subclass,
+            name: d.name.to_string(),
+            description: d.description.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::abilities;
+    use crate::engine::classes::ALL_CLASSES;
+    use crate::engine::talents;
+
+    #[test]
+    fn every_class_has_two_specializations() {
+        for class in ALL_CLASSES {
+            assert_eq!(options(class).len(), 2, "{:?}", class);
+        }
+    }
+
+    #[test]
+    fn definitions_point_at_real_abilities_and_talents() {
+        for def in all_subclasses() {
+            if let Some(id) = def.replaces {
+                assert!(abilities::get_ability(def.class, id).is_some(), "{} replaces unknown {}", def.name, id);
+            }
+            let passive = talents::find_talent(def.class, def.passive)
+                .unwrap_or_else(|| panic!("{} grants unknown {}", def.name, def.passive));
+            assert_eq!(passive.subclass, Some(def.subclass));
+            assert_eq!(abilities::subclass_ability(def.subclass).class, def.class);
+        }
+    }
+}
@@ -0,0 +1,584 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::entity::*;
+
+/// Mechanics a talent switches on rather than a flat stat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TalentPassive {
+    /// Heal on every kill.
+    Bloodlust,
+    /// Extra melee damage against targets suffering a harmful status.
+    Opportunist,
+    /// Regain mana on every kill.
+    ManaSiphon,
+    /// Raised dead and summons serve for longer.
+    LastingServitude,
+    /// Extra melee damage while below half health.
+    Frenzy,
+    /// Statuses the player's abilities inflict last longer.
+    DeepChill,
+}
+
+/// What one rank of a talent grants.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TalentEffect {
+    MaxHp(i32),
+    Attack(i32),
+    Defense(i32),
+    Speed(i32),
+    CritChance(f32),
+    DodgeChance(f32),
+    /// Flat bonus damage on player melee hits.
+    Cleave(i32),
+    /// Bonus damage for damaging spells and scrolls.
+    SpellPower(i32),
+    /// Extra mana regained each turn.
+    ManaRegen(i32),
+    /// Raises the rank of every ability.
+    AbilityRank(u32),
+    /// Adds an ability from `abilities::talent_abilities` to the player's kit.
+    UnlockAbility(&'static str),
+    Passive(TalentPassive),
+}
+
+pub struct Talent {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// None for talents every class can learn.
+    pub class: Option<PlayerClass>,
+    /// Set for talents only that specialization can learn.
+    pub subclass: Option<Subclass>,
+    pub max_rank: u32,
+    /// Other talents (and the rank in each) that must be learned first.
+    pub requires: &'static [(&'static str, u32)],
+    pub effect: TalentEffect,
+}
+
+impl Talent {
+    pub fn prerequisites_met(&self, ranks: &HashMap<String, u32>) -> bool {
+        self.requires
+            .iter()
+            .all(|(id, rank)| ranks.get(*id).copied().unwrap_or(0) >= *rank)
+    }
+}
+
+pub fn all_talents() -> Vec<Talent> {
+    vec![
+        // Shared
+        Talent {
+            id: "toughness", name: "Toughness", description: "+10 max HP per rank.",
+            class: None, subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::MaxHp(10),
+        },
+        Talent {
+            id: "might", name: "Might", description: "+2 attack per rank.",
+            class: None, subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::Attack(2),
+        },
+        Talent {
+            id: "guard", name: "Guard", description: "+2 defense per rank.",
+            class: None, subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::Defense(2),
+        },
+        Talent {
+            id: "swiftness", name: "Swiftness", description: "+15 speed per rank.",
+            class: None, subclass: None, max_rank: 2, requires: &[], effect: TalentEffect::Speed(15),
+        },
+        Talent {
+            id: "empower", name: "Empower", description: "+1 rank to every ability per rank.",
+            class: None, subclass: None, max_rank: 2, requires: &[], effect: TalentEffect::AbilityRank(1),
+        },
+        // Warrior
+        Talent {
+            id: "cleave", name: "Cleave", description: "Melee hits deal +1 damage per rank.",
+            class: Some(PlayerClass::Warrior), subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::Cleave(1),
+        },
+        Talent {
+            id: "fortify", name: "Fortify", description: "+3 defense per rank.",
+            class: Some(PlayerClass::Warrior), subclass: None, max_rank: 2, requires: &[("guard", 1)], effect: TalentEffect::Defense(3),
+        },
+        Talent {
+            id: "bloodlust", name: "Bloodlust", description: "Heal 3 HP per rank whenever you slay a foe.",
+            class: Some(PlayerClass::Warrior), subclass: None, max_rank: 2, requires: &[("cleave", 2)],
+            effect: TalentEffect::Passive(TalentPassive::Bloodlust),
+        },
+        Talent {
+            id: "battle_focus", name: "Battle Focus", description: "Learn Battle Focus: haste yourself for 5 turns.",
+            class: Some(PlayerClass::Warrior), subclass: None, max_rank: 1, requires: &[("might", 2)],
+            effect: TalentEffect::UnlockAbility("battle_focus"),
+        },
+        Talent {
+            id: "chain_hook", name: "Chain Hook", description: "Learn Chain Hook: drag a distant foe to your side.",
+            class: Some(PlayerClass::Warrior), subclass: None, max_rank: 1, requires: &[("fortify", 1)],
+            effect: TalentEffect::UnlockAbility("chain_hook"),
+        },
+        // Rogue
+        Talent {
+            id: "backstab", name: "Backstab", description: "+5% crit chance per rank.",
+            class: Some(PlayerClass::Rogue), subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::CritChance(0.05),
+        },
+        Talent {
+            id: "evasion", name: "Evasion", description: "+5% dodge chance per rank.",
+            class: Some(PlayerClass::Rogue), subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::DodgeChance(0.05),
+        },
+        Talent {
+            id: "opportunist", name: "Opportunist",
+            description: "Melee hits deal +2 damage per rank to stunned, slowed, confused or blinded foes.",
+            class: Some(PlayerClass::Rogue), subclass: None, max_rank: 2, requires: &[("backstab", 2)],
+            effect: TalentEffect::Passive(TalentPassive::Opportunist),
+        },
+        Talent {
+            id: "crippling_throw", name: "Crippling Throw", description: "Learn Crippling Throw: slow a distant foe.",
+            class: Some(PlayerClass::Rogue), subclass: None, max_rank: 1, requires: &[("evasion", 1)],
+            effect: TalentEffect::UnlockAbility("crippling_throw"),
+        },
+        // Mage
+        Talent {
+            id: "spell_power", name: "Spell Power", description: "Damaging spells and scrolls deal +5 damage per rank.",
+            class: Some(PlayerClass::Mage), subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::SpellPower(5),
+        },
+        Talent {
+            id: "mana_regen", name: "Mana Flow", description: "Regain +1 mana per turn per rank.",
+            class: Some(PlayerClass::Mage), subclass: None, max_rank: 2, requires: &[], effect: TalentEffect::ManaRegen(1),
+        },
+        Talent {
+            id: "mana_siphon", name: "Mana Siphon", description: "Regain 3 mana per rank whenever you slay a foe.",
+            class: Some(PlayerClass::Mage), subclass: None, max_rank: 2, requires: &[("mana_regen", 1)],
+            effect: TalentEffect::Passive(TalentPassive::ManaSiphon),
+        },
+        Talent {
+            id: "chain_spark", name: "Chain Spark", description: "Learn Chain Spark: a crackling burst of lightning.",
+            class: Some(PlayerClass::Mage), subclass: None, max_rank: 1, requires: &[("spell_power", 2)],
+            effect: TalentEffect::UnlockAbility("chain_spark"),
+        },
+        // Ranger
+        Talent {
+            id: "marksman", name: "Marksman", description: "+5% crit chance per rank.",
+            class: Some(PlayerClass::Ranger), subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::CritChance(0.05),
+        },
+        Talent {
+            id: "fleet_foot", name: "Fleet Foot", description: "+5% dodge chance per rank.",
+            class: Some(PlayerClass::Ranger), subclass: None, max_rank: 2, requires: &[], effect: TalentEffect::DodgeChance(0.05),
+        },
+        Talent {
+            id: "trailblazer", name: "Trailblazer", description: "+10 speed per rank.",
+            class: Some(PlayerClass::Ranger), subclass: None, max_rank: 2, requires: &[("fleet_foot", 1)], effect: TalentEffect::Speed(10),
+        },
+        Talent {
+            id: "volley", name: "Volley", description: "Learn Volley: rain arrows on a group of foes.",
+            class: Some(PlayerClass::Ranger), subclass: None, max_rank: 1, requires: &[("marksman", 2)],
+            effect: TalentEffect::UnlockAbility("volley"),
+        },
+        // Cleric
+        Talent {
+            id: "devotion", name: "Devotion", description: "Damaging spells and scrolls deal +3 damage per rank.",
+            class: Some(PlayerClass::Cleric), subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::SpellPower(3),
+        },
+        Talent {
+            id: "prayer", name: "Prayer", description: "Regain +1 mana per turn per rank.",
+            class: Some(PlayerClass::Cleric), subclass: None, max_rank: 2, requires: &[], effect: TalentEffect::ManaRegen(1),
+        },
+        Talent {
+            id: "sanctuary", name: "Sanctuary", description: "+3 defense per rank.",
+            class: Some(PlayerClass::Cleric), subclass: None, max_rank: 2, requires: &[("guard", 1)], effect: TalentEffect::Defense(3),
+        },
+        Talent {
+            id: "holy_nova", name: "Holy Nova", description: "Learn Holy Nova: burn every foe around you.",
+            class: Some(PlayerClass::Cleric), subclass: None, max_rank: 1, requires: &[("devotion", 2)],
+            effect: TalentEffect::UnlockAbility("holy_nova"),
+        },
+        Talent {
+            id: "consecration", name: "Consecration", description: "Learn Consecration: hallow the ground around you.",
+            class: Some(PlayerClass::Cleric), subclass: None, max_rank: 1, requires: &[("sanctuary", 1)],
+            effect: TalentEffect::UnlockAbility("consecration"),
+        },
+        // Necromancer
+        Talent {
+            id: "dark_arts", name: "Dark Arts", description: "Damaging spells and scrolls deal +4 damage per rank.",
+            class: Some(PlayerClass::Necromancer), subclass: None, max_rank: 3, requires: &[], effect: TalentEffect::SpellPower(4),
+        },
+        Talent {
+            id: "soul_harvest", name: "Soul Harvest", description: "Regain 3 mana per rank whenever you slay a foe.",
+            class: Some(PlayerClass::Necromancer), subclass: None, max_rank: 2, requires: &[],
+            effect: TalentEffect::Passive(TalentPassive::ManaSiphon),
+        },
+        Talent {
+            id: "lasting_servitude", name: "Lasting Servitude", description: "Raised dead serve 10 turns longer per rank.",
+            class: Some(PlayerClass::Necromancer), subclass: None, max_rank: 2, requires: &[("soul_harvest", 1)],
+            effect: TalentEffect::Passive(TalentPassive::LastingServitude),
+        },
+        Talent {
+            id: "bone_spear", name: "Bone Spear", description: "Learn Bone Spear: impale a distant foe.",
+            class: Some(PlayerClass::Necromancer), subclass: None, max_rank: 1, requires: &[("dark_arts", 2)],
+            effect: TalentEffect::UnlockAbility("bone_spear"),
+        },
+        Talent {
+            id: "bone_servant", name: "Bone Servant", description: "Learn Summon Skeleton: call up a skeletal warrior.",
+            class: Some(PlayerClass::Necromancer), subclass: None, max_rank: 1, requires: &[("lasting_servitude", 1)],
+            effect: TalentEffect::UnlockAbility("summon_skeleton"),
+        },
+        // Specializations: the first talent of each is granted on choosing it
+        Talent {
+            id: "frenzy", name: "Frenzy", description: "Melee hits deal +3 damage per rank while you are below half HP.",
+            class: Some(PlayerClass::Warrior), subclass: Some(Subclass::Berserker), max_rank: 3, requires: &[],
+            effect: TalentEffect::Passive(TalentPassive::Frenzy),
+        },
+        Talent {
+            id: "rampage", name: "Rampage", description: "Melee hits deal +2 damage per rank.",
+            class: Some(PlayerClass::Warrior), subclass: Some(Subclass::Berserker), max_rank: 2, requires: &[("frenzy", 2)],
+            effect: TalentEffect::Cleave(2),
+        },
+        Talent {
+            id: "stalwart", name: "Stalwart", description: "+3 defense per rank.",
+            class: Some(PlayerClass::Warrior), subclass: Some(Subclass::Guardian), max_rank: 3, requires: &[],
+            effect: TalentEffect::Defense(3),
+        },
+        Talent {
+            id: "iron_skin", name: "Iron Skin", description: "+15 max HP per rank.",
+            class: Some(PlayerClass::Warrior), subclass: Some(Subclass::Guardian), max_rank: 2, requires: &[("stalwart", 2)],
+            effect: TalentEffect::MaxHp(15),
+        },
+        Talent {
+            id: "killer_instinct", name: "Killer Instinct", description: "+5% crit chance per rank.",
+            class: Some(PlayerClass::Rogue), subclass: Some(Subclass::Assassin), max_rank: 3, requires: &[],
+            effect: TalentEffect::CritChance(0.05),
+        },
+        Talent {
+            id: "exploit_weakness", name: "Exploit Weakness",
+            description: "Melee hits deal +2 damage per rank to stunned, slowed, confused or blinded foes.",
+            class: Some(PlayerClass::Rogue), subclass: Some(Subclass::Assassin), max_rank: 2, requires: &[("killer_instinct", 2)],
+            effect: TalentEffect::Passive(TalentPassive::Opportunist),
+        },
+        Talent {
+            id: "misdirection", name: "Misdirection", description: "+5% dodge chance per rank.",
+            class: Some(PlayerClass::Rogue), subclass: Some(Subclass::Trickster), max_rank: 3, requires: &[],
+            effect: TalentEffect::DodgeChance(0.05),
+        },
+        Talent {
+            id: "quickstep", name: "Quickstep", description: "+10 speed per rank.",
+            class: Some(PlayerClass::Rogue), subclass: Some(Subclass::Trickster), max_rank: 2, requires: &[("misdirection", 2)],
+            effect: TalentEffect::Speed(10),
+        },
+        Talent {
+            id: "kindling", name: "Kindling", description: "Damaging spells and scrolls deal +4 damage per rank.",
+            class: Some(PlayerClass::Mage), subclass: Some(Subclass::Pyromancer), max_rank: 3, requires: &[],
+            effect: TalentEffect::SpellPower(4),
+        },
+        Talent {
+            id: "inferno", name: "Inferno", description: "+1 rank to every ability.",
+            class: Some(PlayerClass::Mage), subclass: Some(Subclass::Pyromancer), max_rank: 1, requires: &[("kindling", 2)],
+            effect: TalentEffect::AbilityRank(1),
+        },
+        Talent {
+            id: "deep_chill", name: "Deep Chill", description: "Statuses your abilities inflict last 2 turns longer per rank.",
+            class: Some(PlayerClass::Mage), subclass: Some(Subclass::Cryomancer), max_rank: 3, requires: &[],
+            effect: TalentEffect::Passive(TalentPassive::DeepChill),
+        },
+        Talent {
+            id: "ice_armor", name: "Ice Armor", description: "+2 defense per rank.",
+            class: Some(PlayerClass::Mage), subclass: Some(Subclass::Cryomancer), max_rank: 2, requires: &[("deep_chill", 2)],
+            effect: TalentEffect::Defense(2),
+        },
+        Talent {
+            id: "pack_bond", name: "Pack Bond", description: "Summoned allies serve 10 turns longer per rank.",
+            class: Some(PlayerClass::Ranger), subclass: Some(Subclass::Beastmaster), max_rank: 3, requires: &[],
+            effect: TalentEffect::Passive(TalentPassive::LastingServitude),
+        },
+        Talent {
+            id: "wild_heart", name: "Wild Heart", description: "+10 max HP per rank.",
+            class: Some(PlayerClass::Ranger), subclass: Some(Subclass::Beastmaster), max_rank: 2, requires: &[("pack_bond", 2)],
+            effect: TalentEffect::MaxHp(10),
+        },
+        Talent {
+            id: "steady_aim", name: "Steady Aim", description: "+5% crit chance per rank.",
+            class: Some(PlayerClass::Ranger), subclass: Some(Subclass::Sharpshooter), max_rank: 3, requires: &[],
+            effect: TalentEffect::CritChance(0.05),
+        },
+        Talent {
+            id: "deadeye", name: "Deadeye", description: "+2 attack per rank.",
+            class: Some(PlayerClass::Ranger), subclass: Some(Subclass::Sharpshooter), max_rank: 2, requires: &[("steady_aim", 2)],
+            effect: TalentEffect::Attack(2),
+        },
+        Talent {
+            id: "zeal", name: "Zeal", description: "+2 attack per rank.",
+            class: Some(PlayerClass::Cleric), subclass: Some(Subclass::Crusader), max_rank: 3, requires: &[],
+            effect: TalentEffect::Attack(2),
+        },
+        Talent {
+            id: "righteous_fury", name: "Righteous Fury", description: "Heal 3 HP per rank whenever you slay a foe.",
+            class: Some(PlayerClass::Cleric), subclass: Some(Subclass::Crusader), max_rank: 2, requires: &[("zeal", 2)],
+            effect: TalentEffect::Passive(TalentPassive::Bloodlust),
+        },
+        Talent {
+            id: "foresight", name: "Foresight", description: "Regain +1 mana per turn per rank.",
+            class: Some(PlayerClass::Cleric), subclass: Some(Subclass::Oracle), max_rank: 3, requires: &[],
+            effect: TalentEffect::ManaRegen(1),
+        },
+        Talent {
+            id: "blessed_vigor", name: "Blessed Vigor", description: "+10 max HP per rank.",
+            class: Some(PlayerClass::Cleric), subclass: Some(Subclass::Oracle), max_rank: 2, requires: &[("foresight", 2)],
+            effect: TalentEffect::MaxHp(10),
+        },
+        Talent {
+            id: "phylactery", name: "Phylactery", description: "Damaging spells and scrolls deal +4 damage per rank.",
+            class: Some(PlayerClass::Necromancer), subclass: Some(Subclass::Lich), max_rank: 3, requires: &[],
+            effect: TalentEffect::SpellPower(4),
+        },
+        Talent {
+            id: "soul_feast", name: "Soul Feast", description: "Regain 3 mana per rank whenever you slay a foe.",
+            class: Some(PlayerClass::Necromancer), subclass: Some(Subclass::Lich), max_rank: 2, requires: &[("phylactery", 2)],
+            effect: TalentEffect::Passive(TalentPassive::ManaSiphon),
+        },
+        Talent {
+            id: "ossuary", name: "Ossuary", description: "Raised dead and summons serve 10 turns longer per rank.",
+            class: Some(PlayerClass::Necromancer), subclass: Some(Subclass::Bonelord), max_rank: 3, requires: &[],
+            effect: TalentEffect::Passive(TalentPassive::LastingServitude),
+        },
+        Talent {
+            id: "bone_plating", name: "Bone Plating", description: "+3 defense per rank.",
+            class: Some(PlayerClass::Necromancer), subclass: Some(Subclass::Bonelord), max_rank: 2, requires: &[("ossuary", 2)],
+            effect: TalentEffect::Defense(3),
+        },
+    ]
+}
+
+/// The talents a class can learn: the shared ones plus its own.
+pub fn class_tree(class: PlayerClass) -> Vec<Talent> {
+    all_talents()
+        .into_iter()
+        .filter(|t| t.class.is_none_or(|c| c == class))
+        .collect()
+}
+
+pub fn find_talent(class: PlayerClass, id: &str) -> Option<Talent> {
+    class_tree(class).into_iter().find(|t| t.id == id)
+}
+
+/// Apply one rank's entity-level stat bonus. World-level effects (cleave,
+/// spell power, mana regen, abilities, passives) are read from the ranks.
+pub fn apply_rank(entity: &mut Entity, effect: TalentEffect) {
+    if let TalentEffect::MaxHp(amount) = effect {
+        if let Some(ref mut health) = entity.health {
+            health.max += amount;
+            health.current += amount;
+        }
+        return;
+    }
+    if let Some(ref mut combat) = entity.combat {
+        match effect {
+            TalentEffect::Attack(amount) => combat.base_attack += amount,
+            TalentEffect::Defense(amount) => combat.base_defense += amount,
+            TalentEffect::Speed(amount) => combat.base_speed += amount,
+            TalentEffect::CritChance(amount) => combat.crit_chance += amount,
+            TalentEffect::DodgeChance(amount) => combat.dodge_chance += amount,
+            _ => {}
+        }
+    }
+}
+
+/// Sum of a per-rank World-level bonus across learned talents.
+fn total(ranks: &HashMap<String, u32>, class: PlayerClass, pick: impl Fn(TalentEffect) -> Option<i32>) -> i32 {
+    class_tree(class)
+        .into_iter()
+        .filter_map(|t| pick(t.effect).map(|per_rank| per_rank * ranks.get(t.id).copied().unwrap_or(0) as i32))
+        .sum()
+}
+
+pub fn cleave_bonus(ranks: &HashMap<String, u32>, class: PlayerClass) -> i32 {
+    total(ranks, class, |e| match e {
+        TalentEffect::Cleave(n) => Some(n),
+        _ => None,
+    })
+}
+
+pub fn spell_power_bonus(ranks: &HashMap<String, u32>, class: PlayerClass) -> i32 {
+    total(ranks, class, |e| match e {
+        TalentEffect::SpellPower(n) => Some(n),
+        _ => None,
+    })
+}
+
+pub fn mana_regen_bonus(ranks: &HashMap<String, u32>, class: PlayerClass) -> i32 {
+    total(ranks, class, |e| match e {
+        TalentEffect::ManaRegen(n) => Some(n),
+        _ => None,
+    })
+}
+
+pub fn ability_rank_bonus(ranks: &HashMap<String, u32>, class: PlayerClass) -> u32 {
+    total(ranks, class, |e| match e {
+        TalentEffect::AbilityRank(n) => Some(n as i32),
+        _ => None,
+    }) as u32
+}
+
+/// Ranks learned in the talent granting `passive`.
+pub fn passive_rank(ranks: &HashMap<String, u32>, class: PlayerClass, passive: TalentPassive) -> u32 {
+    class_tree(class)
+        .into_iter()
+        .filter(|t| t.effect == TalentEffect::Passive(passive))
+        .map(|t| ranks.get(t.id).copied().unwrap_or(0))
+        .sum()
+}
+
+/// Ability ids unlocked by learned talents.
+pub fn unlocked_abilities(ranks: &HashMap<String, u32>, class: PlayerClass) -> Vec<&'static str> {
+    class_tree(class)
+        .into_iter()
+        .filter(|t| ranks.get(t.id).copied().unwrap_or(0) > 0)
+        .filter_map(|t| match t.effect {
+            TalentEffect::UnlockAbility(id) => Some(id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// One node of the talent tree as shown on level-up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TalentView {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub rank: u32,
+    pub max_rank: u32,
+    /// Prerequisites, e.g. ["Cleave 2"].
+    pub requires: Vec<String>,
+    /// Prerequisites met and ranks left to learn.
+    pub available: bool,
+}
+
+/// The class tree as the player sees it: talents of other specializations
+/// are left out.
+pub fn tree_view(ranks: &HashMap<String, u32>, class: PlayerClass, subclass: Option<Subclass>) -> Vec<TalentView> {
+    let tree = class_tree(class);
+    tree.iter()
+        .filter(|t| t.subclass.is_none() || t.subclass == subclass)
+        .map(|t| {
+            let rank = ranks.get(t.id).copied().unwrap_or(0);
+            TalentView {
+                id: t.id.to_string(),
+                name: t.name.to_string(),
+                description: t.description.to_string(),
+                rank,
+                max_rank: t.max_rank,
+                requires: t
+                    .requires
+                    .iter()
+                    .map(|(id, r)| {
+                        let name = tree.iter().find(|p| p.id == *id).map_or(*id, |p| p.name);
+                        format!("{} {}", name, r)
+                    })
+                    .collect(),
+                available: rank < t.max_rank && t.prerequisites_met(ranks),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::abilities;
+    use crate::engine::classes::ALL_CLASSES;
+
+    fn make_player() -> Entity {
+        Entity {
+            id: 0,
+            name: "Player".to_string(),
+            position: Position::new(5, 5),
+            glyph: 0x40,
+            render_order: RenderOrder::Player,
+            blocks_movement: true,
+            blocks_fov: false,
+            health: Some(Health::new(50)),
+            combat: Some(CombatStats {
+                base_attack: 5,
+                base_defense: 2,
+                base_speed: 100,
+                crit_chance: 0.05,
+                dodge_chance: 0.0,
+                ranged: None,
+                on_hit: None,
+                resistances: Vec::new(),
+            }),
+            ai: None,
+            inventory: Some(Inventory::new(20)),
+            equipment: Some(EquipmentSlots::empty()),
+            item: None,
+            status_effects: Vec::new(),
+            fov: Some(FieldOfView::new(8)),
+            door: None,
+            trap: None,
+            stair: None,
+            loot_table: None,
+            flavor_text: None,
+            shop: None,
+            interactive: None,
+            elite: None,
+            resurrection_timer: None,
+        }
+    }
+
+    #[test]
+    fn trees_are_well_formed() {
+        for class in ALL_CLASSES {
+            let tree = class_tree(class);
+            for talent in &tree {
+                assert!(talent.max_rank > 0);
+                for (id, rank) in talent.requires {
+                    let req = tree.iter().find(|t| t.id == *id).unwrap_or_else(|| panic!("{} needs unknown {}", talent.id, id));
+                    assert!(*rank <= req.max_rank);
+                }
+                if let TalentEffect::UnlockAbility(id) = talent.effect {
+                    assert!(abilities::talent_abilities(class).iter().any(|a| a.id == id), "{} unlocks unknown {}", talent.id, id);
+                }
+            }
+        }
+        assert!(find_talent(PlayerClass::Warrior, "backstab").is_none());
+    }
+
+    #[test]
+    fn prerequisites_gate_deeper_talents() {
+        let mut ranks = HashMap::new();
+        let available = |ranks: &HashMap<String, u32>, id: &str| {
+            tree_view(ranks, PlayerClass::Warrior, None).into_iter().find(|t| t.id == id).unwrap().available
+        };
+        assert!(!available(&ranks, "bloodlust"));
+        ranks.insert("cleave".to_string(), 2);
+        assert!(available(&ranks, "bloodlust"));
+        assert_eq!(cleave_bonus(&ranks, PlayerClass::Warrior), 2);
+        ranks.insert("cleave".to_string(), 3);
+        assert!(!available(&ranks, "cleave"));
+    }
+
+    #[test]
+    fn specialization_talents_only_show_for_their_subclass() {
+        let ranks = HashMap::new();
+        let ids = |subclass| -> Vec<String> {
+            tree_view(&ranks, PlayerClass::Warrior, subclass).into_iter().map(|t| t.id).collect()
+        };
+        assert!(!ids(None).iter().any(|id| id == "frenzy" || id == "stalwart"));
+        assert!(ids(Some(Subclass::Berserker)).iter().any(|id| id == "frenzy"));
+        assert!(!ids(Some(Subclass::Berserker)).iter().any(|id| id == "stalwart"));
+    }
+
+    #[test]
+    fn ranks_raise_player_stats() {
+        let mut player = make_player();
+        apply_rank(&mut player, TalentEffect::MaxHp(10));
+        assert_eq!(player.health.as_ref().unwrap().max, 60);
+        assert_eq!(player.health.as_ref().unwrap().current, 60);
+
+        apply_rank(&mut player, TalentEffect::Attack(2));
+        apply_rank(&mut player, TalentEffect::Defense(2));
+        apply_rank(&mut player, TalentEffect::Speed(15));
+        let combat = player.combat.as_ref().unwrap();
+        assert_eq!(combat.base_attack, 7);
+        assert_eq!(combat.base_defense, 4);
+        assert_eq!(combat.base_speed, 115);
+
+        // World-level effects leave the entity alone
+        apply_rank(&mut player, TalentEffect::Cleave(1));
+        assert_eq!(player.combat.as_ref().unwrap().base_attack, 7);
+    }
+}
@@ -17,6 +17,7 @@ pub fn run() {
             let conn = persistence::database::open_database(&db_path)
                 .expect("Failed to open database");
             engine::achievements::ensure_table(&conn);
+            engine::meta::ensure_table(&conn);
 
             app.manage(commands::AppState {
                 world: Mutex::new(None),
@@ -27,11 +28,15 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::new_game,
+            commands::get_character_options,
             commands::player_action,
             commands::get_game_state,
             commands::save_game,
             commands::load_game,
             commands::inspect_entity,
+            commands::inspect_item,
+            commands::preview_combat,
+            commands::preview_target,
             commands::get_run_history,
             commands::get_high_scores,
             commands::get_settings,
@@ -39,8 +44,12 @@ pub fn run() {
             commands::has_save_game,
             commands::check_ollama,
             commands::get_adjacent_shop,
+            commands::get_recipe_book,
             commands::get_achievements,
             commands::get_unlockables,
+            commands::get_meta_progress,
+            commands::buy_meta_upgrade,
+            commands::reset_meta_progress,
             commands::get_statistics,
             commands::start_daily_challenge,
             commands::get_daily_status,
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 #[allow(dead_code)]
-const SCHEMA_VERSION: u32 = 2;
+const SCHEMA_VERSION: u32 = 5;
 
 /// Open (or create) the database at the given path and run migrations.
 pub fn open_database(path: &Path) -> Result<Connection, String> {
@@ -40,6 +40,15 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
     if current_version < 2 {
         migrate_v2(conn)?;
     }
+    if current_version < 3 {
+        migrate_v3(conn)?;
+    }
+    if current_version < 4 {
+        migrate_v4(conn)?;
+    }
+    if current_version < 5 {
+        migrate_v5(conn)?;
+    }
 
     Ok(())
 }
@@ -115,6 +124,39 @@ fn migrate_v2(conn: &Connection) -> Result<(), String> {
     Ok(())
 }
 
+fn migrate_v3(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "ALTER TABLE runs ADD COLUMN pure INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE high_scores ADD COLUMN pure INTEGER NOT NULL DEFAULT 0;
+
+         INSERT OR REPLACE INTO schema_version (version) VALUES (3);"
+    ).map_err(|e| format!("Migration v3 error: {}", e))?;
+
+    Ok(())
+}
+
+fn migrate_v4(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "ALTER TABLE runs ADD COLUMN subclass TEXT;
+         ALTER TABLE high_scores ADD COLUMN subclass TEXT;
+
+         INSERT OR REPLACE INTO schema_version (version) VALUES (4);"
+    ).map_err(|e| format!("Migration v4 error: {}", e))?;
+
+    Ok(())
+}
+
+fn migrate_v5(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "ALTER TABLE runs ADD COLUMN name TEXT;
+         ALTER TABLE high_scores ADD COLUMN name TEXT;
+
+         INSERT OR REPLACE INTO schema_version (version) VALUES (5);"
+    ).map_err(|e| format!("Migration v5 error: {}", e))?;
+
+    Ok(())
+}
+
 /// Check if an active save exists.
 pub fn has_save(conn: &Connection) -> bool {
     conn.query_row("SELECT COUNT(*) FROM save_state", [], |row| row.get::<_, i64>(0))
@@ -153,33 +195,55 @@ pub fn delete_save(conn: &Connection) -> Result<(), String> {
     Ok(())
 }
 
+/// A finished run, as written to the run history and high scores.
+#[derive(Debug, Clone, Default)]
+pub struct RunRecord<'a> {
+    pub seed: &'a str,
+    pub floor_reached: u32,
+    pub enemies_killed: u32,
+    pub bosses_killed: u32,
+    pub level_reached: u32,
+    pub turns_taken: u32,
+    pub score: u32,
+    pub cause_of_death: Option<&'a str>,
+    pub victory: bool,
+    pub class: &'a str,
+    pub subclass: Option<&'a str>,
+    pub name: Option<&'a str>,
+    pub modifiers: &'a [String],
+    pub pure: bool,
+}
+
 /// Record a completed run.
-pub fn record_run(
-    conn: &Connection,
-    seed: &str,
-    floor_reached: u32,
-    enemies_killed: u32,
-    bosses_killed: u32,
-    level_reached: u32,
-    turns_taken: u32,
-    score: u32,
-    cause_of_death: Option<&str>,
-    victory: bool,
-    class: &str,
-    modifiers: &[String],
-) -> Result<(), String> {
+pub fn record_run(conn: &Connection, run: &RunRecord) -> Result<(), String> {
+    let RunRecord {
+        seed,
+        floor_reached,
+        enemies_killed,
+        bosses_killed,
+        level_reached,
+        turns_taken,
+        score,
+        cause_of_death,
+        victory,
+        class,
+        subclass,
+        name,
+        modifiers,
+        pure,
+    } = *run;
     let modifiers_json = serde_json::to_string(modifiers).unwrap_or_else(|_| "[]".to_string());
     conn.execute(
-        "INSERT INTO runs (seed, floor_reached, enemies_killed, bosses_killed, level_reached, turns_taken, score, cause_of_death, victory, class, modifiers)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-        params![seed, floor_reached, enemies_killed, bosses_killed, level_reached, turns_taken, score, cause_of_death, victory as i32, class, modifiers_json],
+        "INSERT INTO runs (seed, floor_reached, enemies_killed, bosses_killed, level_reached, turns_taken, score, cause_of_death, victory, class, modifiers, pure, subclass, name)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+        params![seed, floor_reached, enemies_killed, bosses_killed, level_reached, turns_taken, score, cause_of_death, victory as i32, class, modifiers_json, pure as i32, subclass, name],
     ).map_err(|e| format!("Record run error: {}", e))?;
 
     // Also insert into high_scores
     conn.execute(
-        "INSERT INTO high_scores (score, floor_reached, seed, victory, class)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![score, floor_reached, seed, victory as i32, class],
+        "INSERT INTO high_scores (score, floor_reached, seed, victory, class, pure, subclass, name)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![score, floor_reached, seed, victory as i32, class, pure as i32, subclass, name],
     ).map_err(|e| format!("Record high score error: {}", e))?;
 
     // Keep only top 10 high scores
@@ -194,7 +258,7 @@ pub fn record_run(
 /// Get top 10 high scores.
 pub fn get_high_scores(conn: &Connection) -> Result<Vec<crate::engine::entity::HighScore>, String> {
     let mut stmt = conn
-        .prepare("SELECT score, floor_reached, seed, victory, timestamp, COALESCE(class, 'Warrior') FROM high_scores ORDER BY score DESC LIMIT 10")
+        .prepare("SELECT score, floor_reached, seed, victory, timestamp, COALESCE(class, 'Warrior'), pure, subclass, name FROM high_scores ORDER BY score DESC LIMIT 10")
         .map_err(|e| format!("Query error: {}", e))?;
 
     let scores = stmt
@@ -207,6 +271,9 @@ pub fn get_high_scores(conn: &Connection) -> Result<Vec<crate::engine::entity::H
                 victory: row.get::<_, i32>(3)? != 0,
                 timestamp: row.get(4)?,
                 class: row.get(5)?,
+                subclass: row.get(7)?,
+                name: row.get(8)?,
+                pure: row.get::<_, i32>(6)? != 0,
             })
         })
         .map_err(|e| format!("Query error: {}", e))?
@@ -224,7 +291,7 @@ pub fn get_high_scores(conn: &Connection) -> Result<Vec<crate::engine::entity::H
 /// Get run history (most recent 50).
 pub fn get_run_history(conn: &Connection) -> Result<Vec<crate::engine::entity::RunSummary>, String> {
     let mut stmt = conn
-        .prepare("SELECT seed, floor_reached, enemies_killed, bosses_killed, level_reached, turns_taken, score, cause_of_death, victory, timestamp, COALESCE(class, 'Warrior'), COALESCE(modifiers, '[]') FROM runs ORDER BY id DESC LIMIT 50")
+        .prepare("SELECT seed, floor_reached, enemies_killed, bosses_killed, level_reached, turns_taken, score, cause_of_death, victory, timestamp, COALESCE(class, 'Warrior'), COALESCE(modifiers, '[]'), pure, subclass, name FROM runs ORDER BY id DESC LIMIT 50")
         .map_err(|e| format!("Query error: {}", e))?;
 
     let runs = stmt
@@ -243,7 +310,10 @@ pub fn get_run_history(conn: &Connection) -> Result<Vec<crate::engine::entity::R
                 victory: row.get::<_, i32>(8)? != 0,
                 timestamp: row.get(9)?,
                 class: row.get(10)?,
+                subclass: row.get(13)?,
+                name: row.get(14)?,
                 modifiers,
+                pure: row.get::<_, i32>(12)? != 0,
             })
         })
         .map_err(|e| format!("Query error: {}", e))?
@@ -381,9 +451,24 @@ mod tests {
     #[test]
     fn record_run_and_history() {
         let conn = test_db();
-        record_run(&conn, "42", 5, 10, 1, 3, 50, 1250, Some("Slain by goblin"), false, "Warrior", &[]).unwrap();
+        record_run(&conn, &RunRecord {
+            seed: "42",
+            floor_reached: 5,
+            enemies_killed: 10,
+            bosses_killed: 1,
+            level_reached: 3,
+            turns_taken: 50,
+            score: 1250,
+            cause_of_death: Some("Slain by goblin"),
+            class: "Warrior",
+            subclass: Some("Guardian"),
+            name: Some("Mira"),
+            ..Default::default()
+        }).unwrap();
         let runs = get_run_history(&conn).unwrap();
         assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].subclass.as_deref(), Some("Guardian"));
+        assert_eq!(runs[0].name.as_deref(), Some("Mira"));
         assert_eq!(runs[0].floor_reached, 5);
         assert_eq!(runs[0].score, 1250);
         assert!(!runs[0].victory);
@@ -392,9 +477,19 @@ mod tests {
     #[test]
     fn high_scores_ranked() {
         let conn = test_db();
-        record_run(&conn, "1", 1, 5, 0, 1, 20, 500, None, false, "Warrior", &[]).unwrap();
-        record_run(&conn, "2", 3, 15, 0, 2, 50, 1500, None, false, "Rogue", &[]).unwrap();
-        record_run(&conn, "3", 10, 50, 3, 5, 200, 5000, None, true, "Mage", &[]).unwrap();
+        record_run(&conn, &RunRecord { seed: "1", floor_reached: 1, score: 500, class: "Warrior", ..Default::default() }).unwrap();
+        record_run(&conn, &RunRecord { seed: "2", floor_reached: 3, score: 1500, class: "Rogue", ..Default::default() }).unwrap();
+        record_run(&conn, &RunRecord {
+            seed: "3",
+            floor_reached: 10,
+            bosses_killed: 3,
+            score: 5000,
+            victory: true,
+            class: "Mage",
+            subclass: Some("Pyromancer"),
+            pure: true,
+            ..Default::default()
+        }).unwrap();
 
         let scores = get_high_scores(&conn).unwrap();
         assert_eq!(scores.len(), 3);
@@ -408,7 +503,8 @@ mod tests {
     fn high_scores_pruned_to_10() {
         let conn = test_db();
         for i in 0..15u32 {
-            record_run(&conn, &i.to_string(), 1, i, 0, 1, 10, i * 100, None, false, "Warrior", &[]).unwrap();
+            let seed = i.to_string();
+            record_run(&conn, &RunRecord { seed: &seed, floor_reached: 1, score: i * 100, class: "Warrior", ..Default::default() }).unwrap();
         }
         let scores = get_high_scores(&conn).unwrap();
         assert_eq!(scores.len(), 10);
@@ -4,6 +4,7 @@ use rand::SeedableRng;
 use rand::rngs::StdRng;
 use rusqlite::Connection;
 
+use crate::engine::meta;
 use crate::engine::state::World;
 use super::database;
 
@@ -47,6 +48,8 @@ pub fn load_world(conn: &Connection) -> Result<Option<World>, String> {
             // Re-seed RNG from seed + turn for determinism
             let combined_seed = world.seed.wrapping_add(world.turn as u64);
             world.rng = StdRng::seed_from_u64(combined_seed);
+            world.refresh_carry_capacity();
+            world.migrate_legacy_progression();
 
             Ok(Some(world))
         }
@@ -78,21 +81,26 @@ pub fn end_run(conn: &Connection, world: &World) -> Result<(), String> {
     };
 
     let class_str = format!("{:?}", world.player_class);
+    let subclass_str = world.subclass.map(|s| format!("{:?}", s));
     let modifier_strs: Vec<String> = world.modifiers.iter().map(|m| format!("{:?}", m)).collect();
-    database::record_run(
-        conn,
-        &world.seed.to_string(),
-        world.floor,
-        world.enemies_killed,
-        world.bosses_killed,
-        world.player_level,
-        world.turn,
+    let seed = world.seed.to_string();
+    database::record_run(conn, &database::RunRecord {
+        seed: &seed,
+        floor_reached: world.floor,
+        enemies_killed: world.enemies_killed,
+        bosses_killed: world.bosses_killed,
+        level_reached: world.player_level,
+        turns_taken: world.turn,
         score,
-        cause,
-        world.victory,
-        &class_str,
-        &modifier_strs,
-    )?;
+        cause_of_death: cause,
+        victory: world.victory,
+        class: &class_str,
+        subclass: subclass_str.as_deref(),
+        name: world.player_name.as_deref(),
+        modifiers: &modifier_strs,
+        pure: world.pure_run,
+    })?;
+    meta::award_shards(conn, world.shards_earned())?;
 
     // Track lifetime stats
     let _ = database::increment_stat(conn, "total_runs", 1);
@@ -138,6 +146,7 @@ mod tests {
     fn in_memory_db() -> Connection {
         let conn = Connection::open_in_memory().unwrap();
         init_schema(&conn).unwrap();
+        meta::ensure_table(&conn);
         conn
     }
 
@@ -189,6 +198,9 @@ mod tests {
         // Should have a run in history
         let runs = database::get_run_history(&conn).unwrap();
         assert_eq!(runs.len(), 1);
+
+        // ...and have paid out meta progression shards
+        assert_eq!(meta::get_status(&conn).shards, world.shards_earned());
     }
 
     #[test]
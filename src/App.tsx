@@ -1,5 +1,5 @@
 import { useState, useCallback, useEffect } from "react";
-import type { AppScreen, LevelUpChoice, PlayerClass, Settings as SettingsType } from "./types/game";
+import type { AppScreen, NewCharacter, PlayerClass, Settings as SettingsType } from "./types/game";
 import { useGameState } from "./hooks/useGameState";
 import { getSettings, getDailyStatus, startDailyChallenge } from "./lib/api";
 import { GameView } from "./components/game/GameView";
@@ -11,6 +11,7 @@ import { Settings } from "./components/menu/Settings";
 import { Achievements } from "./components/menu/Achievements";
 import { ClassSelect } from "./components/menu/ClassSelect";
 import { Statistics } from "./components/menu/Statistics";
+import { Upgrades } from "./components/menu/Upgrades";
 
 function App() {
   const [screen, setScreen] = useState<AppScreen>("menu");
@@ -38,8 +39,8 @@ function App() {
   );
 
   const handleClassSelected = useCallback(
-    async (playerClass: PlayerClass, modifiers: string[]) => {
-      await game.startNewGame(undefined, playerClass, modifiers);
+    async (playerClass: PlayerClass, modifiers: string[], pure: boolean, character: NewCharacter) => {
+      await game.startNewGame(undefined, playerClass, modifiers, pure, character);
       setScreen("game");
     },
     [game],
@@ -68,8 +69,8 @@ function App() {
   }, [game]);
 
   const handleLevelUp = useCallback(
-    (choice: LevelUpChoice) => {
-      game.levelUpChoice(choice);
+    (talentId: string) => {
+      game.levelUpChoice(talentId);
     },
     [game],
   );
@@ -116,6 +117,7 @@ function App() {
           onHighScores={() => setScreen("highscores")}
           onRunHistory={() => setScreen("history")}
           onAchievements={() => setScreen("achievements")}
+          onUpgrades={() => setScreen("upgrades")}
           onStatistics={() => setScreen("statistics")}
           onSettings={() => setScreen("settings")}
         />
@@ -145,13 +147,21 @@ function App() {
           onUseItem={game.useItem}
           onDropItem={game.dropItem}
           onEquipItem={game.equipItem}
+          onCookItem={game.cookItem}
+          onOfferItem={game.offerItem}
+          onIdentifyItem={game.identifyItem}
           onLevelUpChoice={handleLevelUp}
+          onChooseSubclass={game.chooseSubclass}
           onEscape={handleEscape}
           onClickMove={game.clickMove}
           onAutoExplore={game.startAutoExplore}
           onCancelAutoExplore={game.cancelAutoExplore}
           onRangedAttack={game.rangedAttack}
           onInteract={game.interact}
+          onPray={game.pray}
+          onPayTithe={game.payTithe}
+          onRest={game.rest}
+          onRestUntilHealed={game.restUntilHealed}
           onBuyItem={game.buyItem}
           onSellItem={game.sellItem}
           onUseAbility={game.useAbility}
@@ -168,6 +178,8 @@ function App() {
       return <Achievements onBack={handleBackToMenu} />;
     case "statistics":
       return <Statistics onBack={handleBackToMenu} />;
+    case "upgrades":
+      return <Upgrades onBack={handleBackToMenu} />;
     default:
       return null;
   }
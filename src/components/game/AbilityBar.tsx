@@ -12,7 +12,8 @@ export function AbilityBar({ abilities, currentMana, onUseAbility }: AbilityBarP
   return (
     <div style={styles.container}>
       {abilities.map((ability, i) => {
-        const canCast = currentMana >= ability.mana_cost;
+        const canCast = ability.ready && currentMana >= ability.mana_cost;
+        const cooldownText = ability.cooldown > 0 ? `, ${ability.cooldown} turn cooldown` : "";
         return (
           <button
             key={ability.id}
@@ -22,11 +23,18 @@ export function AbilityBar({ abilities, currentMana, onUseAbility }: AbilityBarP
               cursor: canCast ? "pointer" : "not-allowed",
             }}
             onClick={() => canCast && onUseAbility(ability.id)}
-            title={`${ability.description} (${ability.mana_cost} MP)`}
+            title={`${ability.description} (${ability.mana_cost} MP${cooldownText}, rank ${ability.rank})`}
           >
             <span style={styles.hotkey}>{i + 1}</span>
             <span style={styles.name}>{ability.name}</span>
+            {ability.rank > 1 && <span style={styles.rank}>{"+".repeat(ability.rank - 1)}</span>}
             <span style={styles.cost}>{ability.mana_cost}mp</span>
+            {ability.max_charges !== null && (
+              <span style={styles.charges}>{ability.charges}/{ability.max_charges}</span>
+            )}
+            {ability.cooldown_remaining > 0 && (
+              <span style={styles.cooldown}>{ability.cooldown_remaining}t</span>
+            )}
           </button>
         );
       })}
@@ -48,4 +56,7 @@ const styles: Record<string, React.CSSProperties> = {
   hotkey: { color: "#FFD700", fontWeight: "bold" },
   name: { color: "#ccc" },
   cost: { color: "#4488FF", fontSize: "10px" },
+  rank: { color: "#FFD700", fontSize: "10px" },
+  charges: { color: "#88CC88", fontSize: "10px" },
+  cooldown: { color: "#FF8844", fontSize: "10px" },
 };
@@ -32,6 +32,7 @@ export function DeathScreen({ info, onNewGame, onMainMenu }: DeathScreenProps) {
 
       <div style={styles.stats}>
         <StatRow label="Score" value={info.final_score.toString()} />
+        <StatRow label="Shards" value={`+${info.shards_earned}`} />
         <StatRow label="Floor" value={info.run_summary.floor_reached.toString()} />
         <StatRow label="Level" value={info.run_summary.level_reached.toString()} />
         <StatRow label="Enemies" value={info.run_summary.enemies_killed.toString()} />
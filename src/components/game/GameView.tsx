@@ -1,5 +1,5 @@
 import { useState, useCallback, useMemo, useRef, useEffect } from "react";
-import type { GameState, Direction, LevelUpChoice, GameOverInfo, GameEvent, Position, ShopData } from "../../types/game";
+import type { GameState, Direction, GameOverInfo, GameEvent, Position, ShopData, Subclass } from "../../types/game";
 import type { TargetingState } from "../../lib/renderer";
 import { useInput, type InputMode } from "../../hooks/useInput";
 import { useAudio } from "../../hooks/useAudio";
@@ -10,10 +10,14 @@ import { MessageLog } from "./MessageLog";
 import { Minimap } from "./Minimap";
 import { InventoryPanel } from "./InventoryPanel";
 import { LevelUpModal } from "./LevelUpModal";
+import { SubclassModal } from "./SubclassModal";
 import { ShopPanel } from "./ShopPanel";
 import { CraftPanel } from "./CraftPanel";
 import { AbilityBar } from "./AbilityBar";
 
+/** Turns a plain rest (r) lasts; Shift+R rests until healed. */
+const SHORT_REST_TURNS = 20;
+
 interface GameViewProps {
   gameState: GameState;
   gameOver: GameOverInfo | null;
@@ -29,7 +33,11 @@ interface GameViewProps {
   onUseItem: (index: number) => void;
   onDropItem: (index: number) => void;
   onEquipItem: (index: number) => void;
-  onLevelUpChoice: (choice: LevelUpChoice) => void;
+  onCookItem?: (index: number) => void;
+  onOfferItem?: (index: number) => void;
+  onIdentifyItem?: (scrollIndex: number, targetIndex: number) => void;
+  onLevelUpChoice: (talentId: string) => void;
+  onChooseSubclass: (subclass: Subclass) => void;
   onEscape: () => void;
   onClickMove?: (x: number, y: number) => void;
   onInspectEntity?: (entityId: number) => void;
@@ -37,6 +45,10 @@ interface GameViewProps {
   onCancelAutoExplore?: () => void;
   onRangedAttack?: (targetId: number) => void;
   onInteract?: () => void;
+  onPray?: () => void;
+  onPayTithe?: () => void;
+  onRest?: (turns: number) => void;
+  onRestUntilHealed?: () => void;
   onBuyItem?: (shopId: number, index: number) => void;
   onSellItem?: (index: number, shopId: number) => void;
   onUseAbility?: (abilityId: string, target?: Position | null) => void;
@@ -58,7 +70,11 @@ export function GameView({
   onUseItem,
   onDropItem,
   onEquipItem,
+  onCookItem,
+  onOfferItem,
+  onIdentifyItem,
   onLevelUpChoice,
+  onChooseSubclass,
   onEscape,
   onClickMove,
   onInspectEntity,
@@ -66,6 +82,10 @@ export function GameView({
   onCancelAutoExplore,
   onRangedAttack,
   onInteract,
+  onPray,
+  onPayTithe,
+  onRest,
+  onRestUntilHealed,
   onBuyItem,
   onSellItem,
   onUseAbility,
@@ -136,7 +156,9 @@ export function GameView({
     }
   }, [gameState.messages]);
 
-  const mode: InputMode = pendingLevelUp
+  const choosingSubclass = gameState.subclass_choices.length > 0;
+
+  const mode: InputMode = choosingSubclass || pendingLevelUp
     ? "levelup"
     : showInventory
     ? "inventory"
@@ -154,10 +176,26 @@ export function GameView({
       onDropItem,
       onEquipItem,
       onUnequipSlot: () => {},
-      onLevelUpChoice,
+      onLevelUpChoice: (index: number) => {
+        if (gameState.subclass_choices.length > 0) {
+          const choice = gameState.subclass_choices[index];
+          if (choice) {
+            onChooseSubclass(choice.subclass);
+          }
+          return;
+        }
+        const talent = gameState.talent_tree.filter((t) => t.available)[index];
+        if (talent) {
+          onLevelUpChoice(talent.id);
+        }
+      },
       onToggleInventory: () => setShowInventory((v) => !v),
       onToggleInspect: () => {},
       onInteract: () => onInteract?.(),
+      onPray: () => onPray?.(),
+      onPayTithe: () => onPayTithe?.(),
+      onRest: () => { onCancelAutoExplore?.(); onRest?.(SHORT_REST_TURNS); },
+      onRestUntilHealed: () => { onCancelAutoExplore?.(); onRestUntilHealed?.(); },
       onAutoExplore: () => onAutoExplore?.(),
       onUseAbility: (index: number) => {
         const ability = gameState.player.abilities[index];
@@ -208,7 +246,7 @@ export function GameView({
         }
       },
     }),
-    [onMove, onWait, onPickUp, onUseStairs, onUseItem, onDropItem, onEquipItem, onLevelUpChoice, showInventory, targetingMode, targetCursor, visibleEnemies, gameState.player.position, gameState.player.abilities, gameState.visible_entities, onEscape, onInteract, onAutoExplore, onCancelAutoExplore, onRangedAttack, onUseAbility, shopData, craftMode],
+    [onMove, onWait, onPickUp, onUseStairs, onUseItem, onDropItem, onEquipItem, onLevelUpChoice, onChooseSubclass, showInventory, targetingMode, targetCursor, visibleEnemies, gameState.player.position, gameState.player.abilities, gameState.talent_tree, gameState.subclass_choices, gameState.visible_entities, onEscape, onInteract, onPray, onPayTithe, onRest, onRestUntilHealed, onAutoExplore, onCancelAutoExplore, onRangedAttack, onUseAbility, shopData, craftMode],
   );
 
   useInput(mode, inputActions, true);
@@ -295,6 +333,8 @@ export function GameView({
             <div>&gt;: Stairs | i: Inventory</div>
             <div>o: Auto-explore | f: Target</div>
             <div>e: Interact | 1-4: Abilities</div>
+            <div>p: Pray | t: Tithe gold</div>
+            <div>r: Rest | R: Rest until healed</div>
             <div>Esc: Menu</div>
           </div>
         </div>
@@ -313,12 +353,28 @@ export function GameView({
           onUseItem={(i) => { onUseItem(i); setShowInventory(false); }}
           onDropItem={(i) => { onDropItem(i); setShowInventory(false); }}
           onEquipItem={(i) => { onEquipItem(i); setShowInventory(false); }}
+          onCookItem={(i) => { onCookItem?.(i); setShowInventory(false); }}
+          onOfferItem={(i) => { onOfferItem?.(i); setShowInventory(false); }}
+          onIdentifyItem={(s, t) => { onIdentifyItem?.(s, t); setShowInventory(false); }}
           onClose={handleCloseInventory}
         />
       )}
 
-      {pendingLevelUp && (
-        <LevelUpModal level={gameState.player.level} choices={gameState.level_up_choices} onChoice={onLevelUpChoice} />
+      {choosingSubclass && (
+        <SubclassModal
+          playerClass={gameState.player.player_class}
+          choices={gameState.subclass_choices}
+          onChoice={onChooseSubclass}
+        />
+      )}
+
+      {!choosingSubclass && pendingLevelUp && (
+        <LevelUpModal
+          level={gameState.player.level}
+          points={gameState.talent_points}
+          talents={gameState.talent_tree}
+          onChoice={onLevelUpChoice}
+        />
       )}
 
       {shopData && (
@@ -1,5 +1,22 @@
 import { useState } from "react";
-import type { PlayerState } from "../../types/game";
+import type { DeityMood, HungerState, PlayerState } from "../../types/game";
+
+const MOOD_COLORS: Record<DeityMood, string> = {
+  Wrathful: "#FF4444",
+  Displeased: "#FF8844",
+  Indifferent: "#aaa",
+  Pleased: "#44FF44",
+  Exalted: "#FFD700",
+};
+
+const HUNGER_LABELS: Record<HungerState, string> = {
+  Satiated: "Satiated",
+  NotHungry: "Not hungry",
+  Hungry: "Hungry",
+  Weak: "Weak",
+  Fainting: "Fainting",
+  Starving: "Starving",
+};
 
 interface HUDProps {
   player: PlayerState;
@@ -27,7 +44,7 @@ export function HUD({ player, floor, turn, seed }: HUDProps) {
   return (
     <div style={styles.container}>
       <div style={styles.row}>
-        <span style={styles.label}>{player.player_class} </span>
+        <span style={styles.label}>{player.name && `${player.name} the `}{player.subclass ?? player.player_class} </span>
         <span style={styles.label}>Floor {floor}</span>
         <span style={styles.label}>Turn {turn}</span>
         <span
@@ -70,7 +87,7 @@ export function HUD({ player, floor, turn, seed }: HUDProps) {
         <div style={styles.barBg}>
           <div style={{ ...styles.barFill, width: `${hungerPct}%`, backgroundColor: hungerColor }} />
         </div>
-        <span style={styles.value}>{hungerPct > 50 ? "Full" : hungerPct > 25 ? "Hungry" : "Starving"}</span>
+        <span style={styles.value}>{HUNGER_LABELS[player.hunger_state]}</span>
       </div>
 
       <div style={styles.stats}>
@@ -78,8 +95,23 @@ export function HUD({ player, floor, turn, seed }: HUDProps) {
         <span>DEF {player.defense}</span>
         <span>SPD {player.speed}</span>
         <span style={{ color: "#FFD700" }}>Gold {player.gold}</span>
+        <span style={{ color: player.encumbrance === "Unburdened" ? undefined : "#FF8844" }}>
+          WT {player.carry_weight}/{player.carry_capacity}
+        </span>
       </div>
 
+      {player.deity && (
+        <div
+          style={styles.stats}
+          title={`Likes: ${player.deity.likes.join(", ")}\nDislikes: ${player.deity.dislikes.join(", ")}\nBoon: ${player.deity.boon}`}
+        >
+          <span style={{ color: "#c0a060" }}>{player.deity.name}</span>
+          <span style={{ color: MOOD_COLORS[player.deity.mood] }}>{player.deity.mood}</span>
+          <span>Favor {player.deity.favor}</span>
+          {player.deity.prayer_timeout > 0 && <span style={{ color: "#555" }}>Pray in {player.deity.prayer_timeout}</span>}
+        </div>
+      )}
+
       {player.status_effects.length > 0 && (
         <div style={styles.effects}>
           {player.status_effects.map((e, i) => (
@@ -1,3 +1,4 @@
+import { useState } from "react";
 import type { PlayerState, ItemView, EquipmentView } from "../../types/game";
 
 interface InventoryPanelProps {
@@ -5,10 +6,24 @@ interface InventoryPanelProps {
   onUseItem: (index: number) => void;
   onDropItem: (index: number) => void;
   onEquipItem: (index: number) => void;
+  onCookItem: (index: number) => void;
+  onOfferItem: (index: number) => void;
+  onIdentifyItem: (scrollIndex: number, targetIndex: number) => void;
   onClose: () => void;
 }
 
-export function InventoryPanel({ player, onUseItem, onDropItem, onEquipItem, onClose }: InventoryPanelProps) {
+export function InventoryPanel({ player, onUseItem, onDropItem, onEquipItem, onCookItem, onOfferItem, onIdentifyItem, onClose }: InventoryPanelProps) {
+  // Index of the Scroll of Identify waiting for the player to pick its target
+  const [reading, setReading] = useState<number | null>(null);
+
+  const handleUse = (index: number) => {
+    if (player.inventory[index]?.identifies) {
+      setReading(index);
+    } else {
+      onUseItem(index);
+    }
+  };
+
   return (
     <div style={styles.overlay} onClick={onClose}>
       <div style={styles.panel} onClick={(e) => e.stopPropagation()}>
@@ -18,15 +33,35 @@ export function InventoryPanel({ player, onUseItem, onDropItem, onEquipItem, onC
         </div>
 
         <EquipmentDisplay equipment={player.equipment} />
+        {player.set_bonuses.map((bonus) => (
+          <div key={bonus} style={styles.setBonus}>{bonus}</div>
+        ))}
 
         <div style={styles.divider} />
 
+        {reading !== null && (
+          <div style={styles.prompt}>
+            Choose an item to identify.{" "}
+            <button style={styles.actionBtn} onClick={() => setReading(null)}>Cancel</button>
+          </div>
+        )}
+
         {player.inventory.length === 0 ? (
           <div style={styles.empty}>Your inventory is empty.</div>
         ) : (
           <div style={styles.itemList}>
             {player.inventory.map((item, i) => (
-              <ItemRow key={item.id} item={item} index={i} onUse={onUseItem} onDrop={onDropItem} onEquip={onEquipItem} />
+              <ItemRow
+                key={item.id}
+                item={item}
+                index={i}
+                onUse={handleUse}
+                onDrop={onDropItem}
+                onEquip={onEquipItem}
+                onCook={onCookItem}
+                onOffer={player.at_altar ? onOfferItem : undefined}
+                onIdentify={reading !== null && i !== reading && !item.identified ? (t) => onIdentifyItem(reading, t) : undefined}
+              />
             ))}
           </div>
         )}
@@ -39,12 +74,15 @@ export function InventoryPanel({ player, onUseItem, onDropItem, onEquipItem, onC
   );
 }
 
-function ItemRow({ item, index, onUse, onDrop, onEquip }: {
+function ItemRow({ item, index, onUse, onDrop, onEquip, onCook, onOffer, onIdentify }: {
   item: ItemView;
   index: number;
   onUse: (i: number) => void;
   onDrop: (i: number) => void;
   onEquip: (i: number) => void;
+  onCook: (i: number) => void;
+  onOffer?: (i: number) => void;
+  onIdentify?: (i: number) => void;
 }) {
   const isEquippable = item.slot !== null;
   const isUsable = ["Potion", "Scroll", "Food", "Wand"].includes(item.item_type);
@@ -52,11 +90,21 @@ function ItemRow({ item, index, onUse, onDrop, onEquip }: {
   return (
     <div style={styles.itemRow}>
       <span style={styles.itemIndex}>{index + 1}.</span>
-      <span style={styles.itemName}>{item.name}</span>
+      <span style={styles.itemName} title={[item.artifact, ...item.affixes].filter(Boolean).join("\n")}>{item.name}</span>
+      {item.quantity > 1 && <span style={styles.charges}>x{item.quantity}</span>}
       {item.charges !== null && <span style={styles.charges}>({item.charges})</span>}
+      {item.max_durability > 0 && (
+        <span style={item.durability === 0 ? styles.cursed : styles.charges}>
+          [{item.durability}/{item.max_durability}]
+        </span>
+      )}
+      {item.cursed && <span style={styles.cursed}>(cursed)</span>}
       <div style={styles.itemActions}>
         {isUsable && <button style={styles.actionBtn} onClick={() => onUse(index)}>Use</button>}
         {isEquippable && <button style={styles.actionBtn} onClick={() => onEquip(index)}>Equip</button>}
+        {item.cookable && <button style={styles.actionBtn} onClick={() => onCook(index)}>Cook</button>}
+        {onOffer && <button style={styles.actionBtn} onClick={() => onOffer(index)}>Offer</button>}
+        {onIdentify && <button style={styles.actionBtn} onClick={() => onIdentify(index)}>Identify</button>}
         <button style={styles.actionBtn} onClick={() => onDrop(index)}>Drop</button>
       </div>
     </div>
@@ -130,6 +178,10 @@ const styles: Record<string, React.CSSProperties> = {
   equipment: {
     marginBottom: "8px",
   },
+  setBonus: {
+    fontSize: "12px",
+    color: "#7FD67F",
+  },
   sectionTitle: {
     fontSize: "13px",
     color: "#888",
@@ -152,6 +204,11 @@ const styles: Record<string, React.CSSProperties> = {
     borderTop: "1px solid #333",
     margin: "8px 0",
   },
+  prompt: {
+    fontSize: "12px",
+    color: "#FFD700",
+    marginBottom: "4px",
+  },
   empty: {
     color: "#666",
     textAlign: "center" as const,
@@ -182,6 +239,10 @@ const styles: Record<string, React.CSSProperties> = {
     color: "#888",
     fontSize: "11px",
   },
+  cursed: {
+    color: "#AA44FF",
+    fontSize: "11px",
+  },
   itemActions: {
     display: "flex",
     gap: "4px",
@@ -1,38 +1,44 @@
-import type { LevelUpChoice } from "../../types/game";
+import type { TalentView } from "../../types/game";
 
 interface LevelUpModalProps {
   level: number;
-  choices: LevelUpChoice[];
-  onChoice: (choice: LevelUpChoice) => void;
+  points: number;
+  talents: TalentView[];
+  onChoice: (talentId: string) => void;
 }
 
-const CHOICE_INFO: Record<string, { label: string; desc: string; color?: string }> = {
-  MaxHp: { label: "+10 Max HP", desc: "Increases maximum health by 10 and heals 10" },
-  Attack: { label: "+2 Attack", desc: "Increases base attack power by 2" },
-  Defense: { label: "+2 Defense", desc: "Increases base defense by 2" },
-  Speed: { label: "+15 Speed", desc: "Increases action speed by 15" },
-  Cleave: { label: "Cleave", desc: "Attacks hit harder (+2 bonus damage)", color: "#FF4444" },
-  Fortify: { label: "Fortify", desc: "Hardens your defenses (+3 defense)", color: "#FF4444" },
-  Backstab: { label: "Backstab", desc: "Strike weak points (+5% critical chance)", color: "#44FF44" },
-  Evasion: { label: "Evasion", desc: "Dodge incoming attacks (+5% dodge chance)", color: "#44FF44" },
-  SpellPower: { label: "Spell Power", desc: "Increases spell damage (+3 spell power)", color: "#4488FF" },
-  ManaRegen: { label: "Mana Regen", desc: "Faster mana recovery (+1 per turn)", color: "#4488FF" },
-};
-
-export function LevelUpModal({ level, choices, onChoice }: LevelUpModalProps) {
+export function LevelUpModal({ level, points, talents, onChoice }: LevelUpModalProps) {
+  // Hotkeys number the learnable talents only, in tree order
+  let hotkey = 0;
   return (
     <div style={styles.overlay}>
       <div style={styles.panel}>
         <h2 style={styles.title}>Level Up!</h2>
-        <p style={styles.subtitle}>You reached level {level}. Choose an upgrade:</p>
+        <p style={styles.subtitle}>
+          You reached level {level}. Talent points: {points}
+        </p>
         <div style={styles.choices}>
-          {choices.map((choice, i) => {
-            const info = CHOICE_INFO[choice] ?? { label: choice, desc: "" };
+          {talents.map((talent) => {
+            const key = talent.available ? ++hotkey : null;
+            const mastered = talent.rank >= talent.max_rank;
             return (
-              <button key={choice} style={styles.choiceBtn} onClick={() => onChoice(choice)}>
-                <span style={styles.choiceKey}>[{i + 1}]</span>
-                <span style={{ ...styles.choiceLabel, color: info.color ?? "#44FF44" }}>{info.label}</span>
-                <span style={styles.choiceDesc}>{info.desc}</span>
+              <button
+                key={talent.id}
+                style={{ ...styles.choiceBtn, ...(talent.available ? {} : styles.locked) }}
+                disabled={!talent.available}
+                onClick={() => onChoice(talent.id)}
+              >
+                <span style={styles.choiceKey}>{key !== null && key <= 9 ? `[${key}]` : ""}</span>
+                <span style={styles.choiceLabel}>
+                  {talent.name}
+                  <span style={styles.rank}> {talent.rank}/{talent.max_rank}</span>
+                </span>
+                <span style={styles.choiceDesc}>
+                  {talent.description}
+                  {!mastered && talent.requires.length > 0 && !talent.available && (
+                    <span style={styles.requires}> Requires {talent.requires.join(", ")}</span>
+                  )}
+                </span>
               </button>
             );
           })}
@@ -59,7 +65,9 @@ const styles: Record<string, React.CSSProperties> = {
     padding: "24px",
     textAlign: "center" as const,
     fontFamily: "monospace",
-    maxWidth: "420px",
+    maxWidth: "520px",
+    maxHeight: "80vh",
+    overflowY: "auto" as const,
   },
   title: {
     margin: "0 0 8px",
@@ -105,4 +113,15 @@ const styles: Record<string, React.CSSProperties> = {
     color: "#888",
     fontSize: "12px",
   },
+  locked: {
+    opacity: 0.45,
+    cursor: "default",
+  },
+  rank: {
+    color: "#aaa",
+    fontWeight: "normal",
+  },
+  requires: {
+    color: "#FF8844",
+  },
 };
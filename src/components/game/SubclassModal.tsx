@@ -0,0 +1,92 @@
+import type { PlayerClass, Subclass, SubclassView } from "../../types/game";
+
+interface SubclassModalProps {
+  playerClass: PlayerClass;
+  choices: SubclassView[];
+  onChoice: (subclass: Subclass) => void;
+}
+
+export function SubclassModal({ playerClass, choices, onChoice }: SubclassModalProps) {
+  return (
+    <div style={styles.overlay}>
+      <div style={styles.panel}>
+        <h2 style={styles.title}>Specialization</h2>
+        <p style={styles.subtitle}>Choose the path your {playerClass} will follow. This cannot be undone.</p>
+        <div style={styles.choices}>
+          {choices.map((choice, i) => (
+            <button key={choice.subclass} style={styles.choiceBtn} onClick={() => onChoice(choice.subclass)}>
+              <span style={styles.choiceKey}>[{i + 1}]</span>
+              <span style={styles.choiceLabel}>{choice.name}</span>
+              <span style={styles.choiceDesc}>{choice.description}</span>
+            </button>
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+}
+
+const styles: Record<string, React.CSSProperties> = {
+  overlay: {
+    position: "absolute",
+    inset: 0,
+    backgroundColor: "rgba(0,0,0,0.8)",
+    display: "flex",
+    alignItems: "center",
+    justifyContent: "center",
+    zIndex: 20,
+  },
+  panel: {
+    backgroundColor: "#111118",
+    border: "2px solid #c0a060",
+    borderRadius: "8px",
+    padding: "24px",
+    textAlign: "center" as const,
+    fontFamily: "monospace",
+    maxWidth: "520px",
+  },
+  title: {
+    margin: "0 0 8px",
+    color: "#c0a060",
+    fontSize: "22px",
+  },
+  subtitle: {
+    color: "#aaa",
+    fontSize: "14px",
+    margin: "0 0 16px",
+  },
+  choices: {
+    display: "flex",
+    flexDirection: "column" as const,
+    gap: "8px",
+  },
+  choiceBtn: {
+    display: "flex",
+    alignItems: "center",
+    gap: "12px",
+    padding: "10px 16px",
+    backgroundColor: "#1a1a2e",
+    border: "1px solid #444",
+    borderRadius: "4px",
+    cursor: "pointer",
+    fontFamily: "monospace",
+    color: "#ccc",
+    textAlign: "left" as const,
+    transition: "border-color 0.15s",
+  },
+  choiceKey: {
+    color: "#c0a060",
+    fontSize: "14px",
+    minWidth: "24px",
+  },
+  choiceLabel: {
+    color: "#44FF44",
+    fontSize: "14px",
+    fontWeight: "bold",
+    minWidth: "110px",
+  },
+  choiceDesc: {
+    color: "#888",
+    fontSize: "12px",
+  },
+};
@@ -1,8 +1,9 @@
-import { useState } from "react";
-import type { PlayerClass } from "../../types/game";
+import { useState, useEffect } from "react";
+import type { Background, CreationOptions, NewCharacter, PlayerClass, StatAllocation } from "../../types/game";
+import { getCharacterOptions, getMetaProgress } from "../../lib/api";
 
 interface ClassSelectProps {
-  onSelectClass: (playerClass: PlayerClass, modifiers: string[]) => void;
+  onSelectClass: (playerClass: PlayerClass, modifiers: string[], pure: boolean, character: NewCharacter) => void;
   onBack: () => void;
 }
 
@@ -23,21 +24,39 @@ const CLASSES: ClassInfo[] = [
   {
     name: "Warrior",
     hp: 60, atk: 7, def: 4, spd: 90, crit: "5%", dodge: "0%", mana: 30,
-    perks: ["High HP and Defense", "Shield Bash, War Cry, Whirlwind", "Cleave & Fortify upgrades"],
+    perks: ["High HP and Defense", "Shield Bash, War Cry, Whirlwind", "Talents: Cleave, Bloodlust, Battle Focus"],
     color: "#FF4444",
   },
   {
     name: "Rogue",
     hp: 40, atk: 5, def: 2, spd: 120, crit: "15%", dodge: "10%", mana: 25,
-    perks: ["Fast with high crit chance", "Smoke Bomb, Dash, Poison Strike", "Backstab & Evasion upgrades"],
+    perks: ["Fast with high crit chance", "Smoke Bomb, Dash, Poison Strike", "Talents: Backstab, Opportunist, Crippling Throw"],
     color: "#44FF44",
   },
   {
     name: "Mage",
     hp: 35, atk: 3, def: 1, spd: 100, crit: "5%", dodge: "0%", mana: 50,
-    perks: ["Large mana pool", "Fireball, Frost Bolt, Blink, Arcane Shield", "SpellPower & ManaRegen upgrades"],
+    perks: ["Large mana pool", "Fireball, Frost Bolt, Blink, Arcane Shield", "Talents: Spell Power, Mana Siphon, Chain Spark"],
     color: "#4488FF",
   },
+  {
+    name: "Ranger",
+    hp: 45, atk: 5, def: 2, spd: 110, crit: "10%", dodge: "5%", mana: 30,
+    perks: ["Starts with a bow and a wolf companion", "Aimed Shot, Call Companion, Snare", "Talents: Marksman, Trailblazer, Volley"],
+    color: "#88CC44",
+  },
+  {
+    name: "Cleric",
+    hp: 50, atk: 5, def: 3, spd: 95, crit: "5%", dodge: "0%", mana: 40,
+    perks: ["Holy damage burns the undead", "Heal, Turn Undead, Smite", "Talents: Devotion, Sanctuary, Holy Nova"],
+    color: "#FFEE88",
+  },
+  {
+    name: "Necromancer",
+    hp: 35, atk: 3, def: 1, spd: 100, crit: "5%", dodge: "0%", mana: 45,
+    perks: ["Slain foes leave corpses to raise", "Raise Dead, Drain Life, Bone Armor", "Talents: Dark Arts, Lasting Servitude, Bone Spear"],
+    color: "#AA66CC",
+  },
 ];
 
 const MODIFIERS = [
@@ -45,13 +64,54 @@ const MODIFIERS = [
   { id: "Marathon", name: "Marathon", desc: "20 floors instead of 10 (2x score)", color: "#8888FF" },
   { id: "Pacifist", name: "Pacifist", desc: "No XP from kills, XP from exploration (2.5x score)", color: "#44FFFF" },
   { id: "Cursed", name: "Cursed", desc: "Items unidentified until used (1.3x score)", color: "#AA44FF" },
+  { id: "Famine", name: "Famine", desc: "Hunger drains twice as fast (1.4x score)", color: "#CC9944" },
+];
+
+const STATS: { key: keyof StatAllocation; label: string; per: keyof CreationOptions }[] = [
+  { key: "hp", label: "HP", per: "hp_per_point" },
+  { key: "attack", label: "ATK", per: "attack_per_point" },
+  { key: "defense", label: "DEF", per: "defense_per_point" },
+  { key: "speed", label: "SPD", per: "speed_per_point" },
 ];
 
+const NO_POINTS: StatAllocation = { hp: 0, attack: 0, defense: 0, speed: 0 };
+
 export function ClassSelect({ onSelectClass, onBack }: ClassSelectProps) {
   const [selected, setSelected] = useState<PlayerClass>("Warrior");
   const [activeModifiers, setActiveModifiers] = useState<Set<string>>(new Set());
+  const [pure, setPure] = useState(false);
+  const [lockedClasses, setLockedClasses] = useState<PlayerClass[]>([]);
+  const [lockedModifiers, setLockedModifiers] = useState<string[]>([]);
+  const [options, setOptions] = useState<CreationOptions | null>(null);
+  const [name, setName] = useState("");
+  const [background, setBackground] = useState<Background | null>(null);
+  const [allocation, setAllocation] = useState<StatAllocation>(NO_POINTS);
+
+  useEffect(() => {
+    getMetaProgress()
+      .then((s) => {
+        setLockedClasses(s.locked_classes);
+        setLockedModifiers(s.locked_modifiers);
+      })
+      .catch(() => {});
+    getCharacterOptions().then(setOptions).catch(() => {});
+  }, []);
+
+  const pointsLeft = (options?.point_budget ?? 0) - Object.values(allocation).reduce((a, b) => a + b, 0);
+
+  const adjustStat = (key: keyof StatAllocation, delta: number) => {
+    if (delta > 0 && pointsLeft <= 0) return;
+    if (delta < 0 && allocation[key] <= 0) return;
+    setAllocation(prev => ({ ...prev, [key]: prev[key] + delta }));
+  };
+
+  const start = () => {
+    const character: NewCharacter = { name: name.trim() || null, background, allocation };
+    onSelectClass(selected, Array.from(activeModifiers), pure, character);
+  };
 
   const toggleModifier = (id: string) => {
+    if (lockedModifiers.includes(id)) return;
     setActiveModifiers(prev => {
       const next = new Set(prev);
       if (next.has(id)) next.delete(id);
@@ -65,35 +125,82 @@ export function ClassSelect({ onSelectClass, onBack }: ClassSelectProps) {
       <h1 style={styles.title}>Choose Your Class</h1>
 
       <div style={styles.classRow}>
-        {CLASSES.map(cls => (
-          <div
-            key={cls.name}
-            style={{
-              ...styles.classCard,
-              borderColor: selected === cls.name ? cls.color : "#333",
-              backgroundColor: selected === cls.name ? "#1a1a2e" : "#111118",
-            }}
-            onClick={() => setSelected(cls.name)}
-          >
-            <h2 style={{ ...styles.className, color: cls.color }}>{cls.name}</h2>
-            <div style={styles.statGrid}>
-              <span>HP {cls.hp}</span>
-              <span>ATK {cls.atk}</span>
-              <span>DEF {cls.def}</span>
-              <span>SPD {cls.spd}</span>
-              <span>Crit {cls.crit}</span>
-              <span>Dodge {cls.dodge}</span>
-              <span>Mana {cls.mana}</span>
-            </div>
-            <div style={styles.perks}>
-              {cls.perks.map((p, i) => (
-                <div key={i} style={styles.perk}>{p}</div>
-              ))}
+        {CLASSES.map(cls => {
+          const locked = lockedClasses.includes(cls.name);
+          return (
+            <div
+              key={cls.name}
+              style={{
+                ...styles.classCard,
+                borderColor: selected === cls.name ? cls.color : "#333",
+                backgroundColor: selected === cls.name ? "#1a1a2e" : "#111118",
+                opacity: locked ? 0.4 : 1,
+                cursor: locked ? "not-allowed" : "pointer",
+              }}
+              onClick={() => !locked && setSelected(cls.name)}
+              title={locked ? "Unlock from Upgrades" : undefined}
+            >
+              <h2 style={{ ...styles.className, color: cls.color }}>{cls.name}{locked && " (locked)"}</h2>
+              <div style={styles.statGrid}>
+                <span>HP {cls.hp}</span>
+                <span>ATK {cls.atk}</span>
+                <span>DEF {cls.def}</span>
+                <span>SPD {cls.spd}</span>
+                <span>Crit {cls.crit}</span>
+                <span>Dodge {cls.dodge}</span>
+                <span>Mana {cls.mana}</span>
+              </div>
+              <div style={styles.perks}>
+                {cls.perks.map((p, i) => (
+                  <div key={i} style={styles.perk}>{p}</div>
+                ))}
+              </div>
             </div>
-          </div>
-        ))}
+          );
+        })}
       </div>
 
+      {options && (
+        <>
+          <h2 style={styles.subtitle}>Character (Optional)</h2>
+          <input
+            style={styles.nameInput}
+            placeholder="Name"
+            value={name}
+            maxLength={options.max_name_len}
+            onChange={e => setName(e.target.value)}
+          />
+          <div style={styles.modRow}>
+            {options.backgrounds.map(bg => (
+              <div
+                key={bg.background}
+                style={{
+                  ...styles.modCard,
+                  width: "200px",
+                  borderColor: background === bg.background ? "#FFD700" : "#333",
+                  opacity: background === bg.background ? 1 : 0.6,
+                }}
+                onClick={() => setBackground(background === bg.background ? null : bg.background)}
+              >
+                <span style={{ color: "#FFD700", fontWeight: "bold" }}>{bg.name}</span>
+                <span style={styles.modDesc}>{bg.description}</span>
+                <span style={styles.modDesc}>Talent: {bg.talent}</span>
+              </div>
+            ))}
+          </div>
+          <div style={styles.pointRow}>
+            <span style={styles.pointsLeft}>Points: {pointsLeft}</span>
+            {STATS.map(stat => (
+              <span key={stat.key} style={styles.pointStat}>
+                <button style={styles.pointBtn} onClick={() => adjustStat(stat.key, -1)}>-</button>
+                {stat.label} +{allocation[stat.key] * (options[stat.per] as number)}
+                <button style={styles.pointBtn} onClick={() => adjustStat(stat.key, 1)}>+</button>
+              </span>
+            ))}
+          </div>
+        </>
+      )}
+
       <h2 style={styles.subtitle}>Run Modifiers (Optional)</h2>
       <div style={styles.modRow}>
         {MODIFIERS.map(mod => (
@@ -102,23 +209,31 @@ export function ClassSelect({ onSelectClass, onBack }: ClassSelectProps) {
             style={{
               ...styles.modCard,
               borderColor: activeModifiers.has(mod.id) ? mod.color : "#333",
-              opacity: activeModifiers.has(mod.id) ? 1 : 0.6,
+              opacity: activeModifiers.has(mod.id) ? 1 : lockedModifiers.includes(mod.id) ? 0.25 : 0.6,
+              cursor: lockedModifiers.includes(mod.id) ? "not-allowed" : "pointer",
             }}
             onClick={() => toggleModifier(mod.id)}
           >
-            <span style={{ color: mod.color, fontWeight: "bold" }}>{mod.name}</span>
+            <span style={{ color: mod.color, fontWeight: "bold" }}>
+              {mod.name}{lockedModifiers.includes(mod.id) && " (locked)"}
+            </span>
             <span style={styles.modDesc}>{mod.desc}</span>
           </div>
         ))}
       </div>
 
+      <label style={styles.pure}>
+        <input type="checkbox" checked={pure} onChange={e => setPure(e.target.checked)} />
+        Pure run: no upgrades or unlock rewards
+      </label>
+
       <div style={styles.buttons}>
         <button style={styles.btn} onClick={onBack}>Back</button>
         <button
           style={{ ...styles.btn, ...styles.startBtn }}
-          onClick={() => onSelectClass(selected, Array.from(activeModifiers))}
+          onClick={start}
         >
-          Start as {selected}
+          Start as {name.trim() || selected}
         </button>
       </div>
     </div>
@@ -133,7 +248,7 @@ const styles: Record<string, React.CSSProperties> = {
   },
   title: { fontSize: "28px", color: "#FFD700", marginBottom: "24px" },
   subtitle: { fontSize: "18px", color: "#888", marginTop: "24px", marginBottom: "12px" },
-  classRow: { display: "flex", gap: "16px" },
+  classRow: { display: "flex", flexWrap: "wrap" as const, justifyContent: "center", gap: "16px", maxWidth: "760px" },
   classCard: {
     border: "2px solid #333", borderRadius: "8px", padding: "16px",
     width: "220px", cursor: "pointer", transition: "border-color 0.15s",
@@ -152,6 +267,19 @@ const styles: Record<string, React.CSSProperties> = {
     fontSize: "12px", transition: "opacity 0.15s",
   },
   modDesc: { color: "#666", fontSize: "10px" },
+  nameInput: {
+    padding: "6px 10px", marginBottom: "12px", width: "220px",
+    backgroundColor: "#111118", border: "1px solid #444", borderRadius: "4px",
+    color: "#ccc", fontFamily: "monospace", fontSize: "14px",
+  },
+  pointRow: { display: "flex", alignItems: "center", gap: "16px", marginTop: "12px", fontSize: "12px" },
+  pointsLeft: { color: "#FFD700" },
+  pointStat: { display: "flex", alignItems: "center", gap: "6px", color: "#aaa" },
+  pointBtn: {
+    width: "20px", height: "20px", padding: 0, border: "1px solid #444", borderRadius: "3px",
+    backgroundColor: "#1a1a2e", color: "#ccc", cursor: "pointer", fontFamily: "monospace",
+  },
+  pure: { display: "flex", alignItems: "center", gap: "6px", marginTop: "20px", fontSize: "12px", color: "#88CCFF", cursor: "pointer" },
   buttons: { display: "flex", gap: "16px", marginTop: "32px" },
   btn: {
     padding: "10px 24px", border: "1px solid #444", borderRadius: "4px",
@@ -25,6 +25,7 @@ export function HighScores({ onBack }: HighScoresProps) {
             <tr>
               <th style={styles.th}>#</th>
               <th style={styles.th}>Score</th>
+              <th style={styles.th}>Class</th>
               <th style={styles.th}>Floor</th>
               <th style={styles.th}>Seed</th>
               <th style={styles.th}>Result</th>
@@ -35,10 +36,12 @@ export function HighScores({ onBack }: HighScoresProps) {
               <tr key={s.rank}>
                 <td style={styles.td}>{s.rank}</td>
                 <td style={styles.tdScore}>{s.score}</td>
+                <td style={styles.td}>{s.name ? `${s.name}, ${s.subclass ?? s.class}` : s.subclass ?? s.class}</td>
                 <td style={styles.td}>{s.floor_reached}</td>
                 <td style={styles.td}>{s.seed}</td>
                 <td style={{ ...styles.td, color: s.victory ? "#FFD700" : "#FF4444" }}>
                   {s.victory ? "Victory" : "Death"}
+                  {s.pure && <span style={styles.pure}> (pure)</span>}
                 </td>
               </tr>
             ))}
@@ -93,6 +96,10 @@ const styles: Record<string, React.CSSProperties> = {
     color: "#FFD700",
     fontWeight: "bold",
   },
+  pure: {
+    color: "#88CCFF",
+    fontSize: "11px",
+  },
   btn: {
     padding: "10px 32px",
     backgroundColor: "#1a1a2e",
@@ -8,11 +8,12 @@ interface MainMenuProps {
   onHighScores: () => void;
   onRunHistory: () => void;
   onAchievements: () => void;
+  onUpgrades: () => void;
   onStatistics: () => void;
   onSettings: () => void;
 }
 
-export function MainMenu({ onNewGame, onContinue, onDailyChallenge, onHighScores, onRunHistory, onAchievements, onStatistics, onSettings }: MainMenuProps) {
+export function MainMenu({ onNewGame, onContinue, onDailyChallenge, onHighScores, onRunHistory, onAchievements, onUpgrades, onStatistics, onSettings }: MainMenuProps) {
   const [hasSave, setHasSave] = useState(false);
   useEffect(() => {
     hasSaveGame().then(setHasSave).catch(() => setHasSave(false));
@@ -49,6 +50,9 @@ export function MainMenu({ onNewGame, onContinue, onDailyChallenge, onHighScores
         <button style={styles.btnSmall} onClick={onAchievements}>
           Achievements
         </button>
+        <button style={styles.btnSmall} onClick={onUpgrades}>
+          Upgrades
+        </button>
         <button style={styles.btnSmall} onClick={onStatistics}>
           Statistics
         </button>
@@ -38,7 +38,7 @@ export function RunHistory({ onBack }: RunHistoryProps) {
                 <span style={styles.score}>Score: {run.score}</span>
               </div>
               <div style={styles.runDetails}>
-                Floor {run.floor_reached} | Lv{run.level_reached} |
+                {run.name && `${run.name}, `}{run.subclass ? `${run.class} (${run.subclass})` : run.class} | Floor {run.floor_reached} | Lv{run.level_reached} |
                 {run.enemies_killed} kills | {run.turns_taken} turns
               </div>
               <div style={styles.seedRow}>
@@ -0,0 +1,181 @@
+import { useState, useEffect } from "react";
+import type { MetaStatus } from "../../types/game";
+import { getMetaProgress, buyMetaUpgrade, resetMetaProgress } from "../../lib/api";
+
+interface UpgradesProps {
+  onBack: () => void;
+}
+
+export function Upgrades({ onBack }: UpgradesProps) {
+  const [status, setStatus] = useState<MetaStatus | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getMetaProgress().then(setStatus).catch(() => {});
+  }, []);
+
+  const buy = (id: string) => {
+    buyMetaUpgrade(id)
+      .then((s) => {
+        setStatus(s);
+        setError(null);
+      })
+      .catch((err) => setError(String(err)));
+  };
+
+  const reset = () => {
+    if (!confirm("Refund every upgrade?")) return;
+    resetMetaProgress().then(setStatus).catch((err) => setError(String(err)));
+  };
+
+  return (
+    <div style={styles.container}>
+      <h2 style={styles.title}>Upgrades</h2>
+      <p style={styles.subtitle}>
+        {status?.shards ?? 0} shards &middot; earned at the end of every run
+      </p>
+      {error && <p style={styles.error}>{error}</p>}
+
+      <div style={styles.grid}>
+        {status?.upgrades.map((u) => {
+          const maxed = u.next_cost === null;
+          const affordable = !maxed && (status.shards >= (u.next_cost ?? 0));
+          return (
+            <div key={u.id} style={u.rank > 0 ? styles.card : styles.cardLocked}>
+              <div style={styles.cardHeader}>
+                <span style={u.rank > 0 ? styles.name : styles.nameLocked}>
+                  {u.name} {u.max_rank > 1 && `${u.rank}/${u.max_rank}`}
+                </span>
+                {maxed ? (
+                  <span style={styles.check}>&#10003;</span>
+                ) : (
+                  <button
+                    style={{ ...styles.buyBtn, opacity: affordable ? 1 : 0.4 }}
+                    disabled={!affordable}
+                    onClick={() => buy(u.id)}
+                  >
+                    {u.next_cost} shards
+                  </button>
+                )}
+              </div>
+              <p style={styles.desc}>{u.description}</p>
+            </div>
+          );
+        })}
+      </div>
+
+      <p style={styles.note}>Start a pure run from class select to play without these bonuses.</p>
+
+      <div style={styles.buttons}>
+        <button style={styles.btn} onClick={reset}>
+          Refund All
+        </button>
+        <button style={styles.btn} onClick={onBack}>
+          Back
+        </button>
+      </div>
+    </div>
+  );
+}
+
+const styles: Record<string, React.CSSProperties> = {
+  container: {
+    display: "flex",
+    flexDirection: "column",
+    alignItems: "center",
+    height: "100vh",
+    backgroundColor: "#0a0a12",
+    fontFamily: "monospace",
+    padding: "24px",
+    overflowY: "auto",
+  },
+  title: {
+    fontSize: "28px",
+    color: "#c0a060",
+    margin: "0 0 4px",
+  },
+  subtitle: {
+    fontSize: "14px",
+    color: "#888",
+    margin: "0 0 24px",
+  },
+  error: {
+    fontSize: "12px",
+    color: "#FF6644",
+    margin: "0 0 12px",
+  },
+  grid: {
+    display: "flex",
+    flexDirection: "column" as const,
+    gap: "6px",
+    width: "100%",
+    maxWidth: "500px",
+  },
+  card: {
+    backgroundColor: "#1a1a2e",
+    border: "1px solid #c0a060",
+    borderRadius: "4px",
+    padding: "8px 12px",
+  },
+  cardLocked: {
+    backgroundColor: "#111118",
+    border: "1px solid #333",
+    borderRadius: "4px",
+    padding: "8px 12px",
+  },
+  cardHeader: {
+    display: "flex",
+    justifyContent: "space-between",
+    alignItems: "center",
+  },
+  name: {
+    fontSize: "14px",
+    color: "#c0a060",
+    fontWeight: "bold",
+  },
+  nameLocked: {
+    fontSize: "14px",
+    color: "#888",
+    fontWeight: "bold",
+  },
+  check: {
+    color: "#44FF44",
+    fontSize: "16px",
+  },
+  desc: {
+    fontSize: "12px",
+    color: "#888",
+    margin: "2px 0 0",
+  },
+  buyBtn: {
+    padding: "2px 10px",
+    backgroundColor: "#1a1a2e",
+    border: "1px solid #c0a060",
+    borderRadius: "3px",
+    color: "#c0a060",
+    fontFamily: "monospace",
+    fontSize: "11px",
+    cursor: "pointer",
+  },
+  note: {
+    fontSize: "11px",
+    color: "#555",
+    marginTop: "16px",
+    fontStyle: "italic",
+  },
+  buttons: {
+    display: "flex",
+    gap: "16px",
+    marginTop: "16px",
+  },
+  btn: {
+    padding: "8px 24px",
+    backgroundColor: "#111118",
+    border: "1px solid #333",
+    borderRadius: "4px",
+    color: "#888",
+    fontFamily: "monospace",
+    fontSize: "13px",
+    cursor: "pointer",
+  },
+};
@@ -5,11 +5,12 @@ import type {
   GameOverInfo,
   GameEvent,
   Direction,
-  LevelUpChoice,
   EquipSlot,
+  NewCharacter,
   PlayerAction,
   PlayerClass,
   Position,
+  Subclass,
 } from "../types/game";
 import * as api from "../lib/api";
 
@@ -18,7 +19,7 @@ interface UseGameStateReturn {
   gameOver: GameOverInfo | null;
   events: GameEvent[];
   loading: boolean;
-  startNewGame: (seed?: string, playerClass?: PlayerClass, modifiers?: string[]) => Promise<void>;
+  startNewGame: (seed?: string, playerClass?: PlayerClass, modifiers?: string[], pure?: boolean, character?: NewCharacter) => Promise<void>;
   continueGame: () => Promise<boolean>;
   move: (dir: Direction) => Promise<void>;
   wait: () => Promise<void>;
@@ -27,9 +28,17 @@ interface UseGameStateReturn {
   useItem: (index: number) => Promise<void>;
   dropItem: (index: number) => Promise<void>;
   equipItem: (index: number) => Promise<void>;
+  cookItem: (index: number) => Promise<void>;
+  offerItem: (index: number) => Promise<void>;
+  identifyItem: (scrollIndex: number, targetIndex: number) => Promise<void>;
   unequipSlot: (slot: EquipSlot) => Promise<void>;
-  levelUpChoice: (choice: LevelUpChoice) => Promise<void>;
+  levelUpChoice: (talentId: string) => Promise<void>;
+  chooseSubclass: (subclass: Subclass) => Promise<void>;
   interact: () => Promise<void>;
+  pray: () => Promise<void>;
+  payTithe: () => Promise<void>;
+  rest: (turns: number) => Promise<void>;
+  restUntilHealed: () => Promise<void>;
   clickMove: (x: number, y: number) => Promise<void>;
   rangedAttack: (targetId: number) => Promise<void>;
   buyItem: (shopId: number, index: number) => Promise<void>;
@@ -54,10 +63,10 @@ export function useGameState(): UseGameStateReturn {
     setGameOver(result.game_over);
   }, []);
 
-  const startNewGame = useCallback(async (seed?: string, playerClass?: PlayerClass, modifiers?: string[]) => {
+  const startNewGame = useCallback(async (seed?: string, playerClass?: PlayerClass, modifiers?: string[], pure?: boolean, character?: NewCharacter) => {
     setLoading(true);
     try {
-      const result = await api.newGame(seed, playerClass, modifiers);
+      const result = await api.newGame(seed, playerClass, modifiers, pure, character);
       handleResult(result);
     } finally {
       setLoading(false);
@@ -94,9 +103,20 @@ export function useGameState(): UseGameStateReturn {
   const useItem = useCallback((index: number) => doAction(api.useItemAction(index)), [doAction]);
   const dropItem = useCallback((index: number) => doAction(api.dropItemAction(index)), [doAction]);
   const equipItem = useCallback((index: number) => doAction(api.equipItemAction(index)), [doAction]);
+  const cookItem = useCallback((index: number) => doAction(api.cookAction(index)), [doAction]);
+  const offerItem = useCallback((index: number) => doAction(api.offerAction(index)), [doAction]);
+  const identifyItem = useCallback(
+    (scrollIndex: number, targetIndex: number) => doAction(api.identifyItemAction(scrollIndex, targetIndex)),
+    [doAction],
+  );
   const unequipSlot = useCallback((slot: EquipSlot) => doAction(api.unequipSlotAction(slot)), [doAction]);
-  const levelUpChoice = useCallback((choice: LevelUpChoice) => doAction(api.levelUpAction(choice)), [doAction]);
+  const levelUpChoice = useCallback((talentId: string) => doAction(api.levelUpAction(talentId)), [doAction]);
+  const chooseSubclass = useCallback((subclass: Subclass) => doAction(api.chooseSubclassAction(subclass)), [doAction]);
   const interact = useCallback(() => doAction(api.interactAction()), [doAction]);
+  const pray = useCallback(() => doAction(api.prayAction()), [doAction]);
+  const payTithe = useCallback(() => doAction(api.payTitheAction()), [doAction]);
+  const rest = useCallback((turns: number) => doAction(api.restAction(turns)), [doAction]);
+  const restUntilHealed = useCallback(() => doAction(api.restUntilHealedAction()), [doAction]);
   const clickMove = useCallback((x: number, y: number) => doAction(api.clickMoveAction(x, y)), [doAction]);
   const rangedAttack = useCallback((targetId: number) => doAction(api.rangedAttackAction(targetId)), [doAction]);
   const buyItem = useCallback((shopId: number, index: number) => doAction(api.buyItemAction(shopId, index)), [doAction]);
@@ -157,9 +177,17 @@ export function useGameState(): UseGameStateReturn {
     useItem,
     dropItem,
     equipItem,
+    cookItem,
+    offerItem,
+    identifyItem,
     unequipSlot,
     levelUpChoice,
+    chooseSubclass,
     interact,
+    pray,
+    payTithe,
+    rest,
+    restUntilHealed,
     clickMove,
     rangedAttack,
     buyItem,
@@ -1,5 +1,5 @@
 import { useEffect, useCallback, useRef } from "react";
-import type { Direction, LevelUpChoice } from "../types/game";
+import type { Direction } from "../types/game";
 
 export type InputMode = "normal" | "inventory" | "inspect" | "levelup" | "menu" | "targeting";
 
@@ -12,13 +12,18 @@ interface InputActions {
   onDropItem: (index: number) => void;
   onEquipItem: (index: number) => void;
   onUnequipSlot: () => void;
-  onLevelUpChoice: (choice: LevelUpChoice) => void;
+  /** 0-based index into the talents currently available to learn. */
+  onLevelUpChoice: (index: number) => void;
   onToggleInventory: () => void;
   onToggleInspect: () => void;
   onEscape: () => void;
   onCanvasClick?: (tileX: number, tileY: number) => void;
   onCanvasRightClick?: (tileX: number, tileY: number) => void;
   onInteract?: () => void;
+  onPray?: () => void;
+  onPayTithe?: () => void;
+  onRest?: () => void;
+  onRestUntilHealed?: () => void;
   onAutoExplore?: () => void;
   onEnterTargeting?: () => void;
   onTargetMove?: (dir: Direction) => void;
@@ -111,6 +116,26 @@ export function useInput(mode: InputMode, actions: InputActions, enabled: boolea
           actions.onInteract?.();
           return;
         }
+        if (key === "p" || key === "P") {
+          event.preventDefault();
+          actions.onPray?.();
+          return;
+        }
+        if (key === "t" || key === "T") {
+          event.preventDefault();
+          actions.onPayTithe?.();
+          return;
+        }
+        if (key === "r") {
+          event.preventDefault();
+          actions.onRest?.();
+          return;
+        }
+        if (key === "R") {
+          event.preventDefault();
+          actions.onRestUntilHealed?.();
+          return;
+        }
         if (key === "o" || key === "O") {
           event.preventDefault();
           actions.onAutoExplore?.();
@@ -166,16 +191,11 @@ export function useInput(mode: InputMode, actions: InputActions, enabled: boolea
       }
 
       if (mode === "levelup") {
-        // Dynamic level-up choices: 1-based index mapped by onLevelUpChoice
+        // Talents are numbered 1-9 in the order the modal lists them
         const num = parseInt(key);
         if (num >= 1 && num <= 9) {
           event.preventDefault();
-          // The modal handles index→choice mapping; use fallback mapping for backwards compat
-          const fallback: Record<string, LevelUpChoice> = { "1": "MaxHp", "2": "Attack", "3": "Defense", "4": "Speed" };
-          const choice = fallback[key];
-          if (choice) {
-            actions.onLevelUpChoice(choice);
-          }
+          actions.onLevelUpChoice(num - 1);
           return;
         }
       }
@@ -58,7 +58,7 @@ export function queueAnimationsFromEvents(events: GameEvent[], entities?: Entity
         } else if (dmgType === "poison") {
           color = "#33FF33";
           spawnParticles("puff", pos.x, pos.y, 25);
-        } else if (dmgType === "ice") {
+        } else if (dmgType === "cold") {
           color = "#88CCFF";
           spawnParticles("ice", pos.x, pos.y, 15);
         }
@@ -4,7 +4,13 @@ import type {
   GameState,
   PlayerAction,
   PlayerClass,
+  Subclass,
   EntityDetail,
+  ItemDetail,
+  ItemSource,
+  CombatPreview,
+  TargetSource,
+  TargetPreview,
   RunSummary,
   HighScore,
   Settings,
@@ -12,24 +18,39 @@ import type {
   ShopData,
   AchievementStatus,
   UnlockStatus,
+  MetaStatus,
+  NewCharacter,
+  CreationOptions,
   LifetimeStats,
   DailyStatus,
   Direction,
   EquipSlot,
-  LevelUpChoice,
   Position,
+  RecipeView,
 } from "../types/game";
 
 // --- Game commands ---
 
-export async function newGame(seed?: string, playerClass?: PlayerClass, modifiers?: string[]): Promise<TurnResult> {
+export async function newGame(
+  seed?: string,
+  playerClass?: PlayerClass,
+  modifiers?: string[],
+  pure?: boolean,
+  character?: NewCharacter,
+): Promise<TurnResult> {
   return invoke<TurnResult>("new_game", {
     seed: seed ?? null,
     class: playerClass ?? null,
     modifiers: modifiers ?? null,
+    pure: pure ?? null,
+    character: character ?? null,
   });
 }
 
+export async function getCharacterOptions(): Promise<CreationOptions> {
+  return invoke<CreationOptions>("get_character_options");
+}
+
 export async function playerAction(action: PlayerAction): Promise<TurnResult> {
   return invoke<TurnResult>("player_action", { action });
 }
@@ -50,6 +71,22 @@ export async function inspectEntity(entityId: number): Promise<EntityDetail | nu
   return invoke<EntityDetail | null>("inspect_entity", { entity_id: entityId });
 }
 
+export async function inspectItem(source: ItemSource): Promise<ItemDetail> {
+  return invoke<ItemDetail>("inspect_item", { source });
+}
+
+export async function previewCombat(attackerId: number, targetId: number, abilityId?: string): Promise<CombatPreview> {
+  return invoke<CombatPreview>("preview_combat", {
+    attacker_id: attackerId,
+    target_id: targetId,
+    ability_id: abilityId ?? null,
+  });
+}
+
+export async function previewTarget(source: TargetSource, target: Position): Promise<TargetPreview> {
+  return invoke<TargetPreview>("preview_target", { source, target });
+}
+
 // --- History & Scores ---
 
 export async function getRunHistory(): Promise<RunSummary[]> {
@@ -82,6 +119,10 @@ export async function getAdjacentShop(): Promise<ShopData | null> {
   return invoke<ShopData | null>("get_adjacent_shop");
 }
 
+export async function getRecipeBook(): Promise<RecipeView[]> {
+  return invoke<RecipeView[]>("get_recipe_book");
+}
+
 // --- Ollama ---
 
 export async function checkOllama(): Promise<OllamaStatus> {
@@ -122,8 +163,12 @@ export function unequipSlotAction(slot: EquipSlot): PlayerAction {
   return { action_type: { UnequipSlot: slot } };
 }
 
-export function levelUpAction(choice: LevelUpChoice): PlayerAction {
-  return { action_type: { LevelUpChoice: choice } };
+export function levelUpAction(talentId: string): PlayerAction {
+  return { action_type: { LearnTalent: { talent_id: talentId } } };
+}
+
+export function chooseSubclassAction(subclass: Subclass): PlayerAction {
+  return { action_type: { ChooseSubclass: { subclass } } };
 }
 
 export function clickMoveAction(x: number, y: number): PlayerAction {
@@ -138,10 +183,30 @@ export function rangedAttackAction(targetId: number): PlayerAction {
   return { action_type: { RangedAttack: { target_id: targetId } } };
 }
 
+export function throwItemAction(itemIdx: number, target: Position): PlayerAction {
+  return { action_type: { Throw: { item_idx: itemIdx, target } } };
+}
+
 export function interactAction(): PlayerAction {
   return { action_type: "Interact" };
 }
 
+export function prayAction(): PlayerAction {
+  return { action_type: "Pray" };
+}
+
+export function payTitheAction(): PlayerAction {
+  return { action_type: "PayTithe" };
+}
+
+export function restAction(turns: number): PlayerAction {
+  return { action_type: { Rest: { turns } } };
+}
+
+export function restUntilHealedAction(): PlayerAction {
+  return { action_type: "RestUntilHealed" };
+}
+
 export function buyItemAction(shopId: number, index: number): PlayerAction {
   return { action_type: { BuyItem: { shop_id: shopId, index } } };
 }
@@ -160,6 +225,18 @@ export async function getUnlockables(): Promise<UnlockStatus[]> {
   return invoke<UnlockStatus[]>("get_unlockables");
 }
 
+export async function getMetaProgress(): Promise<MetaStatus> {
+  return invoke<MetaStatus>("get_meta_progress");
+}
+
+export async function buyMetaUpgrade(upgradeId: string): Promise<MetaStatus> {
+  return invoke<MetaStatus>("buy_meta_upgrade", { upgrade_id: upgradeId });
+}
+
+export async function resetMetaProgress(): Promise<MetaStatus> {
+  return invoke<MetaStatus>("reset_meta_progress");
+}
+
 // --- Statistics ---
 
 export async function getStatistics(): Promise<LifetimeStats> {
@@ -178,6 +255,30 @@ export function craftAction(weaponIdx: number, scrollIdx: number): PlayerAction
   return { action_type: { Craft: { weapon_idx: weaponIdx, scroll_idx: scrollIdx } } };
 }
 
+export function repairAction(itemIdx: number): PlayerAction {
+  return { action_type: { Repair: { item_idx: itemIdx } } };
+}
+
+export function salvageAction(itemIdx: number): PlayerAction {
+  return { action_type: { Salvage: { item_idx: itemIdx } } };
+}
+
+export function cookAction(itemIdx: number): PlayerAction {
+  return { action_type: { Cook: { item_idx: itemIdx } } };
+}
+
+export function offerAction(itemIdx: number): PlayerAction {
+  return { action_type: { Offer: { item_idx: itemIdx } } };
+}
+
+export function identifyItemAction(scrollIdx: number, targetIdx: number): PlayerAction {
+  return { action_type: { IdentifyItem: { scroll_idx: scrollIdx, target_idx: targetIdx } } };
+}
+
+export function craftRecipeAction(recipeId: string, targetIdx?: number | null): PlayerAction {
+  return { action_type: { CraftRecipe: { recipe_id: recipeId, target_idx: targetIdx ?? null } } };
+}
+
 // --- Daily Challenge ---
 
 export async function startDailyChallenge(): Promise<TurnResult> {
@@ -9,7 +9,11 @@ export type Direction = "N" | "S" | "E" | "W" | "NE" | "NW" | "SE" | "SW";
 
 export type EntityType = "Player" | "Enemy" | "Item" | "Door" | "Trap" | "Stairs" | "Interactive";
 
-export type ItemType = "Weapon" | "Armor" | "Shield" | "Ring" | "Amulet" | "Potion" | "Scroll" | "Wand" | "Key" | "Food" | "Projectile";
+export type ItemType = "Weapon" | "Armor" | "Shield" | "Ring" | "Amulet" | "Potion" | "Scroll" | "Wand" | "Key" | "Food" | "Projectile" | "Material" | "Bag";
+
+export type Encumbrance = "Unburdened" | "Burdened" | "Stressed" | "Overloaded";
+
+export type HungerState = "Satiated" | "NotHungry" | "Hungry" | "Weak" | "Fainting" | "Starving";
 
 export type EquipSlot = "MainHand" | "OffHand" | "Head" | "Body" | "Ring" | "Amulet";
 
@@ -25,28 +29,60 @@ export type StatusType =
   | "Hasted"
   | "Slowed"
   | "Shielded"
-  | "Invisible";
+  | "Invisible"
+  | "Distracted"
+  | "Diseased"
+  | "FireWard"
+  | "FrostWard"
+  | "Hunger";
+
+/** Lowercase damage type name carried by `Attacked` events. */
+export type DamageType = "physical" | "fire" | "cold" | "poison" | "lightning" | "necrotic" | "holy";
 
 export type LogSeverity = "Info" | "Warning" | "Danger" | "Good";
 
-export type LevelUpChoice = "MaxHp" | "Attack" | "Defense" | "Speed" | "Cleave" | "Fortify" | "Backstab" | "Evasion" | "SpellPower" | "ManaRegen";
+export type PlayerClass = "Warrior" | "Rogue" | "Mage" | "Ranger" | "Cleric" | "Necromancer";
+
+export type Subclass =
+  | "Berserker"
+  | "Guardian"
+  | "Assassin"
+  | "Trickster"
+  | "Pyromancer"
+  | "Cryomancer"
+  | "Beastmaster"
+  | "Sharpshooter"
+  | "Crusader"
+  | "Oracle"
+  | "Lich"
+  | "Bonelord";
+
+export type Background = "Soldier" | "Scholar" | "Thief";
 
-export type PlayerClass = "Warrior" | "Rogue" | "Mage";
+export type Deity = "Solara" | "Aurum" | "Korr";
 
-export type RunModifier = "GlassCannon" | "Marathon" | "Pacifist" | "Cursed";
+export type DeityMood = "Wrathful" | "Displeased" | "Indifferent" | "Pleased" | "Exalted";
+
+export type RunModifier = "GlassCannon" | "Marathon" | "Pacifist" | "Cursed" | "Famine";
 
 // --- Action types (sent to backend) ---
 
 export type PlayerActionType =
   | { Move: Direction }
   | "Wait"
+  | { Rest: { turns: number } }
+  | "RestUntilHealed"
   | "PickUp"
   | "UseStairs"
   | { UseItem: number }
   | { DropItem: number }
+  | { DropItems: { index: number; count: number } }
   | { EquipItem: number }
   | { UnequipSlot: EquipSlot }
-  | { LevelUpChoice: LevelUpChoice }
+  | { LearnTalent: { talent_id: string } }
+  | { ChooseSubclass: { subclass: Subclass } }
+  | "Pray"
+  | "PayTithe"
   | { ClickMove: { x: number; y: number } }
   | "AutoExplore"
   | { RangedAttack: { target_id: number } }
@@ -54,7 +90,14 @@ export type PlayerActionType =
   | { SellItem: { index: number; shop_id: number } }
   | "Interact"
   | { UseAbility: { ability_id: string; target: Position | null } }
-  | { Craft: { weapon_idx: number; scroll_idx: number } };
+  | { Craft: { weapon_idx: number; scroll_idx: number } }
+  | { Throw: { item_idx: number; target: Position } }
+  | { Repair: { item_idx: number } }
+  | { Salvage: { item_idx: number } }
+  | { Cook: { item_idx: number } }
+  | { CraftRecipe: { recipe_id: string; target_idx: number | null } }
+  | { Offer: { item_idx: number } }
+  | { IdentifyItem: { scroll_idx: number; target_idx: number } };
 
 export interface PlayerAction {
   action_type: PlayerActionType;
@@ -64,11 +107,15 @@ export interface PlayerAction {
 
 export type AutoExploreInterrupt = "EnemySpotted" | "TookDamage" | "ItemFound" | "StairsReached" | "FullyExplored" | "NothingReachable";
 
+/** Why a rest ended before its time was up. */
+export type RestInterrupt = "EnemySpotted" | "TookDamage" | "Hungry";
+
 export interface TurnResult {
   state: GameState;
   events: GameEvent[];
   game_over: GameOverInfo | null;
   auto_explore_interrupt: AutoExploreInterrupt | null;
+  rest_interrupt: RestInterrupt | null;
 }
 
 export interface GameState {
@@ -82,7 +129,53 @@ export interface GameState {
   pending_level_up: boolean;
   biome: Biome;
   seed: number;
-  level_up_choices: LevelUpChoice[];
+  /** Unspent talent points. */
+  talent_points: number;
+  talent_tree: TalentView[];
+  /** Specializations on offer; empty unless the choice is due. */
+  subclass_choices: SubclassView[];
+  turn_order: TurnOrderEntry[];
+}
+
+/** One node of the class talent tree. */
+export interface TalentView {
+  id: string;
+  name: string;
+  description: string;
+  rank: number;
+  max_rank: number;
+  /** Prerequisites, e.g. "Cleave 2". */
+  requires: string[];
+  /** Prerequisites met and ranks left to learn. */
+  available: boolean;
+}
+
+export interface DeityStatus {
+  deity: Deity;
+  name: string;
+  title: string;
+  favor: number;
+  mood: DeityMood;
+  likes: string[];
+  dislikes: string[];
+  boon: string;
+  wrath: string;
+  /** Turns until the patron will hear another prayer. */
+  prayer_timeout: number;
+}
+
+/** One specialization as offered at the milestone. */
+export interface SubclassView {
+  subclass: Subclass;
+  name: string;
+  description: string;
+}
+
+/** Upcoming turn; time_until is in game-time units (100 = one normal turn). */
+export interface TurnOrderEntry {
+  entity_id: number;
+  name: string;
+  time_until: number;
 }
 
 export interface PlayerState {
@@ -96,15 +189,28 @@ export interface PlayerState {
   xp: number;
   xp_to_next: number;
   gold: number;
+  scrap: number;
   inventory: ItemView[];
   equipment: EquipmentView;
+  carry_weight: number;
+  carry_capacity: number;
+  encumbrance: Encumbrance;
+  set_bonuses: string[];
   status_effects: StatusView[];
   player_class: PlayerClass;
+  subclass: Subclass | null;
+  name: string | null;
+  background: Background | null;
+  /** Patron god and standing with them, once pledged at an altar. */
+  deity: DeityStatus | null;
   mana: number;
   max_mana: number;
   abilities: AbilityView[];
   hunger: number;
   max_hunger: number;
+  hunger_state: HungerState;
+  /** Standing by an altar that will still take an offering. */
+  at_altar: boolean;
 }
 
 export interface AbilityView {
@@ -112,6 +218,12 @@ export interface AbilityView {
   name: string;
   mana_cost: number;
   description: string;
+  rank: number;
+  cooldown: number;
+  cooldown_remaining: number;
+  charges: number | null;
+  max_charges: number | null;
+  ready: boolean;
 }
 
 export interface VisibleTile {
@@ -141,7 +253,18 @@ export interface ItemView {
   item_type: ItemType;
   slot: EquipSlot | null;
   charges: number | null;
+  quantity: number;
+  durability: number;
+  max_durability: number;
   identified: boolean;
+  cursed: boolean;
+  affixes: string[];
+  artifact: string | null;
+  weight: number;
+  /** A raw corpse that can go over a fire. */
+  cookable: boolean;
+  /** A known Scroll of Identify, read on a chosen pack item. */
+  identifies: boolean;
 }
 
 export interface EquipmentView {
@@ -177,7 +300,7 @@ export interface MinimapData {
 
 export type GameEvent =
   | { Moved: { entity_id: number; from: Position; to: Position } }
-  | { Attacked: { attacker_id: number; target_id: number; damage: number; killed: boolean; damage_type: string; dodged: boolean } }
+  | { Attacked: { attacker_id: number; target_id: number; damage: number; killed: boolean; damage_type: DamageType; dodged: boolean } }
   | { DamageTaken: { entity_id: number; amount: number; source: string } }
   | { Healed: { entity_id: number; amount: number } }
   | { ItemPickedUp: { item: ItemView } }
@@ -198,17 +321,26 @@ export type GameEvent =
   | { ItemBought: { name: string; price: number } }
   | { ItemSold: { name: string; price: number } }
   | { GoldGained: { amount: number } }
+  | { GoldLost: { amount: number } }
   | { BarrelSmashed: { position: Position; dropped_item: string | null } }
   | { LeverPulled: { position: Position } }
   | { FountainUsed: { position: Position; effect: string } }
   | { ChestOpened: { position: Position; items: string[]; trapped: boolean } }
   | { AltarOffering: { item_name: string; stat_gained: string } }
+  | { PrayerAnswered: { deity: Deity; wrath: boolean; effect: string } }
+  | { Rested: { turns: number; hp_restored: number; mana_restored: number } }
   | { AchievementUnlocked: { name: string } }
   | { AbilityUsed: { name: string; position: Position; targets: Position[] } }
   | { ManaChanged: { amount: number } }
   | { HungerChanged: { level: number } }
   | { SecretRoomFound: { position: Position } }
   | { ItemEnchanted: { item_name: string; new_level: number } }
+  | { ItemRepaired: { item_name: string } }
+  | { ItemSalvaged: { item_name: string; scrap: number } }
+  | { RecipeDiscovered: { recipe_name: string } }
+  | { ItemCrafted: { recipe_name: string } }
+  | { CurseRevealed: { item_name: string } }
+  | { CurseRemoved: { item_name: string } }
   | { BossSummon: { boss_name: string; summoned: string[] } }
   | { BossCharge: { boss_id: number; from: Position; to: Position } }
   | "Victory";
@@ -219,6 +351,7 @@ export interface GameOverInfo {
   cause_of_death: string;
   epitaph: string | null;
   final_score: number;
+  shards_earned: number;
   run_summary: RunSummary;
 }
 
@@ -234,7 +367,11 @@ export interface RunSummary {
   victory: boolean;
   timestamp: string;
   class: string;
+  subclass: string | null;
+  /** Name the player gave their character, if any. */
+  name: string | null;
   modifiers: string[];
+  pure: boolean;
 }
 
 export interface HighScore {
@@ -245,6 +382,9 @@ export interface HighScore {
   timestamp: string;
   victory: boolean;
   class: string;
+  subclass: string | null;
+  name: string | null;
+  pure: boolean;
 }
 
 export interface ShopItem {
@@ -260,6 +400,18 @@ export interface ShopData {
   items: ShopItem[];
 }
 
+export type CraftStation = "Anywhere" | "Anvil" | "Altar";
+
+export interface RecipeView {
+  id: string;
+  name: string;
+  station: CraftStation;
+  ingredients: string[];
+  needs_target: boolean;
+  has_ingredients: boolean;
+  craftable: boolean;
+}
+
 export interface EntityDetail {
   id: number;
   name: string;
@@ -269,6 +421,79 @@ export interface EntityDetail {
   defense: number | null;
   status_effects: StatusView[];
   flavor_text: string | null;
+  affixes: string[];
+}
+
+export type ItemSource =
+  | { Inventory: { index: number } }
+  | { Shop: { shop_id: number; index: number } }
+  | { Floor: { entity_id: number } };
+
+export interface ItemComparison {
+  equipped_name: string | null;
+  attack_delta: number;
+  defense_delta: number;
+  speed_delta: number;
+}
+
+export interface ItemDetail {
+  name: string;
+  item_type: ItemType;
+  slot: EquipSlot | null;
+  identified: boolean;
+  power: number;
+  speed_mod: number;
+  enchant_level: number;
+  effect: string | null;
+  charges: number | null;
+  ranged: { range: number; damage_bonus: number } | null;
+  quantity: number;
+  weight: number;
+  durability: number;
+  max_durability: number;
+  cursed: boolean;
+  affixes: string[];
+  artifact: string | null;
+  price: number | null;
+  comparison: ItemComparison | null;
+}
+
+export interface AttackForecast {
+  damage_type: DamageType;
+  min_damage: number;
+  max_damage: number;
+  avg_damage: number;
+  hit_chance: number;
+  crit_chance: number;
+  kill_chance: number;
+  hits_to_kill: number | null;
+}
+
+export interface CombatPreview {
+  attacker_id: number;
+  target_id: number;
+  ability_id: string | null;
+  attack: AttackForecast;
+  counter: AttackForecast | null;
+}
+
+export type TargetSource =
+  | { Ability: { ability_id: string } }
+  | { Item: { index: number } };
+
+export interface PreviewHit {
+  entity_id: number;
+  name: string;
+  friendly: boolean;
+}
+
+export interface TargetPreview {
+  valid: boolean;
+  reason: string | null;
+  center: Position;
+  tiles: Position[];
+  hits: PreviewHit[];
+  warnings: string[];
 }
 
 export interface Settings {
@@ -321,6 +546,55 @@ export interface UnlockStatus {
   unlocked: boolean;
 }
 
+export interface MetaUpgradeView {
+  id: string;
+  name: string;
+  description: string;
+  rank: number;
+  max_rank: number;
+  next_cost: number | null;
+}
+
+export interface MetaStatus {
+  shards: number;
+  upgrades: MetaUpgradeView[];
+  locked_classes: PlayerClass[];
+  locked_modifiers: RunModifier[];
+}
+
+/** Free points spent on starting stats at character creation. */
+export interface StatAllocation {
+  hp: number;
+  attack: number;
+  defense: number;
+  speed: number;
+}
+
+/** The character creation screen's picks, sent with `new_game`. */
+export interface NewCharacter {
+  name: string | null;
+  background: Background | null;
+  allocation: StatAllocation;
+}
+
+export interface BackgroundView {
+  background: Background;
+  name: string;
+  description: string;
+  items: string[];
+  talent: string;
+}
+
+export interface CreationOptions {
+  backgrounds: BackgroundView[];
+  point_budget: number;
+  hp_per_point: number;
+  attack_per_point: number;
+  defense_per_point: number;
+  speed_per_point: number;
+  max_name_len: number;
+}
+
 export type LifetimeStats = Record<string, number>;
 
-export type AppScreen = "menu" | "game" | "death" | "victory" | "highscores" | "history" | "settings" | "achievements" | "classSelect" | "statistics";
+export type AppScreen = "menu" | "game" | "death" | "victory" | "highscores" | "history" | "settings" | "achievements" | "classSelect" | "statistics" | "upgrades";